@@ -1,8 +1,11 @@
 use std::{fmt::Display, path::PathBuf};
 
 use hemtt_workspace::reporting::Processed;
+use serde::Serialize;
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+use crate::{fix::Fix, rules::Code};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
 /// Annotation for a CI environment
 ///
 /// <https://github.com/actions/toolkit/tree/main/packages/core#annotations>
@@ -23,6 +26,33 @@ pub struct Annotation {
     pub message: String,
     /// The annotation title
     pub title: String,
+    /// The rule that produced the annotation
+    pub rule: String,
+    /// The code of the finding
+    pub code: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Edit that resolves the finding, applied with `--fix`
+    pub fix: Option<Fix>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    /// Other locations involved in the finding, in the same file
+    pub related: Vec<Related>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+/// Another location involved in a finding, like the second of two overlapping objects
+pub struct Related {
+    /// The path of the file
+    pub path: String,
+    /// The start line of the location
+    pub start_line: usize,
+    /// The end line of the location
+    pub end_line: usize,
+    /// The start column of the location
+    pub start_column: usize,
+    /// The end column of the location
+    pub end_column: usize,
+    /// What is at the location
+    pub message: String,
 }
 
 impl Annotation {
@@ -31,90 +61,60 @@ impl Annotation {
         path: String,
         range: std::ops::Range<usize>,
         message: String,
-        level: Level,
+        code: &'static Code,
     ) -> Self {
-        let start = range.start;
-        let end = range.end;
-        if let Some(processed) = processed {
-            if let Some(start) = processed.mapping(start) {
-                if let Some(end) = processed.mapping(end) {
-                    return Self {
-                        path,
-                        start_line: start.original().start().line(),
-                        end_line: end.original().end().line(),
-                        start_column: start.original().start().column(),
-                        end_column: end.original().end().column(),
-                        level,
-                        message,
-                        title: String::new(),
-                    };
-                }
-            }
-        }
-        let content = if PathBuf::from(&path).is_file() {
-            match std::fs::read_to_string(&path) {
-                Ok(content) => content,
-                Err(e) => {
-                    println!("failed to read {}: {}", path, e);
-                    return Self {
-                        path,
-                        start_line: 1,
-                        end_line: 1,
-                        start_column: 1,
-                        end_column: 1,
-                        level,
-                        message,
-                        title: String::new(),
-                    };
-                }
-            }
-        } else {
-            return Self {
-                path,
-                start_line: 1,
-                end_line: 1,
-                start_column: 1,
-                end_column: 1,
-                level,
-                message,
-                title: String::new(),
-            };
-        };
-        let mut start_line = 1;
-        let mut start_column = 1;
-        let mut end_line = 1;
-        let mut end_column = 1;
-        let mut line_counter = 1;
-        let mut column_counter = 1;
-
-        for (i, c) in content.chars().enumerate() {
-            if i == start {
-                start_line = line_counter;
-                start_column = column_counter;
-            }
-            if i == end {
-                end_line = line_counter;
-                end_column = column_counter;
-            }
-            if c == '\n' {
-                line_counter += 1;
-                column_counter = 1;
-            } else {
-                column_counter += 1;
-            }
-        }
+        let (start_line, end_line, start_column, end_column) = locate(processed, &path, range);
         Self {
             path,
             start_line,
             end_line,
             start_column,
             end_column,
-            level,
+            level: code.level,
             message,
-            title: String::new(),
+            title: code.heading(),
+            rule: code.rule.to_string(),
+            code: code.code.to_string(),
+            fix: None,
+            related: Vec::new(),
         }
     }
 
+    #[must_use]
+    /// Attach the edit that resolves the finding
+    pub fn with_fix(mut self, fix: Option<Fix>) -> Self {
+        self.fix = fix;
+        self
+    }
+
+    #[must_use]
+    /// Add another location in the same file that is involved in the finding
+    pub fn with_related(
+        mut self,
+        processed: Option<&Processed>,
+        range: std::ops::Range<usize>,
+        message: String,
+    ) -> Self {
+        let (start_line, end_line, start_column, end_column) = locate(processed, &self.path, range);
+        self.related.push(Related {
+            path: self.path.clone(),
+            start_line,
+            end_line,
+            start_column,
+            end_column,
+            message,
+        });
+        self
+    }
+
+    #[must_use]
+    /// Check if an ID refers to the annotation's rule, code or slug
+    pub fn matches(&self, id: &str) -> bool {
+        self.rule == id
+            || self.code == id
+            || crate::rules::code(&self.code).is_some_and(|code| code.slug == id)
+    }
+
     #[must_use]
     /// Generate a line for the CI annotation
     pub fn line(&self) -> String {
@@ -132,8 +132,68 @@ impl Annotation {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-/// Annotation level
+/// Find the lines and columns of a range, as `(start_line, end_line, start_column, end_column)`
+///
+/// Ranges in processed output are mapped back to the original file, other ranges are offsets
+/// into the file at `path`. Falls back to the start of the file.
+fn locate(
+    processed: Option<&Processed>,
+    path: &str,
+    range: std::ops::Range<usize>,
+) -> (usize, usize, usize, usize) {
+    let start = range.start;
+    let end = range.end;
+    if let Some(processed) = processed {
+        if let Some(start) = processed.mapping(start) {
+            if let Some(end) = processed.mapping(end) {
+                return (
+                    start.original().start().line(),
+                    end.original().end().line(),
+                    start.original().start().column(),
+                    end.original().end().column(),
+                );
+            }
+        }
+    }
+    if !PathBuf::from(path).is_file() {
+        return (1, 1, 1, 1);
+    }
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => {
+            eprintln!("failed to read {}: {}", path, e);
+            return (1, 1, 1, 1);
+        }
+    };
+    let mut start_line = 1;
+    let mut start_column = 1;
+    let mut end_line = 1;
+    let mut end_column = 1;
+    let mut line_counter = 1;
+    let mut column_counter = 1;
+
+    for (i, c) in content.chars().enumerate() {
+        if i == start {
+            start_line = line_counter;
+            start_column = column_counter;
+        }
+        if i == end {
+            end_line = line_counter;
+            end_column = column_counter;
+        }
+        if c == '\n' {
+            line_counter += 1;
+            column_counter = 1;
+        } else {
+            column_counter += 1;
+        }
+    }
+    (start_line, end_line, start_column, end_column)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+/// Annotation level, ordered from least to most severe
 pub enum Level {
     /// Annotate a notice
     Notice,
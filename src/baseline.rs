@@ -0,0 +1,150 @@
+use std::{collections::HashMap, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    annotation::Annotation,
+    report::{MissionReport, Report},
+};
+
+/// Version of the baseline file format
+pub const BASELINE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+/// Known findings that are hidden from a review
+pub struct Baseline {
+    /// Version of the baseline file format
+    pub version: u32,
+    /// Known findings, a fingerprint is listed once for each time it was found
+    pub findings: Vec<Fingerprint>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+/// Identifies a finding without its span or message, so it survives unrelated edits to the file
+pub struct Fingerprint {
+    /// The mission folder, forward slashed
+    pub mission: String,
+    /// The code of the finding
+    pub code: String,
+    /// The file relative to the mission
+    pub file: String,
+    /// The trimmed source line the finding points at, like `type="O_Soldier_F";` for an object,
+    /// empty when the finding is not at a location in the file
+    pub subject: String,
+}
+
+impl Fingerprint {
+    /// Fingerprint a finding, `content` is the text of the file the finding is in
+    pub fn new(mission: &MissionReport, annotation: &Annotation, content: Option<&str>) -> Self {
+        let mission_path = mission.path.replace('\\', "/");
+        let mission_path = mission_path.trim_start_matches("./");
+        let path = annotation.path.replace('\\', "/");
+        let path = path.trim_start_matches("./");
+        let file = path
+            .strip_prefix(mission_path)
+            .map_or(path, |file| file.trim_start_matches('/'));
+        // Messages can hold counts and distances that change with unrelated edits, the line
+        // that is pointed at only changes when the finding itself does
+        let located = (annotation.start_line, annotation.start_column)
+            != (annotation.end_line, annotation.end_column);
+        let subject = content
+            .filter(|_| located)
+            .and_then(|content| content.lines().nth(annotation.start_line.checked_sub(1)?))
+            .map_or("", str::trim);
+        Self {
+            mission: mission_path.to_string(),
+            code: annotation.code.clone(),
+            file: file.to_string(),
+            subject: subject.to_string(),
+        }
+    }
+}
+
+#[derive(Default)]
+/// The content of the files findings are in, each read once
+struct Sources(HashMap<String, Option<String>>);
+
+impl Sources {
+    fn fingerprint(&mut self, mission: &MissionReport, annotation: &Annotation) -> Fingerprint {
+        let content = self
+            .0
+            .entry(annotation.path.clone())
+            .or_insert_with(|| std::fs::read_to_string(&annotation.path).ok());
+        Fingerprint::new(mission, annotation, content.as_deref())
+    }
+}
+
+impl Baseline {
+    /// Snapshot all findings of a report
+    pub fn new(report: &Report) -> Self {
+        let mut sources = Sources::default();
+        let mut findings = report
+            .missions
+            .iter()
+            .flat_map(|mission| {
+                mission
+                    .annotations
+                    .iter()
+                    .map(move |annotation| (mission, annotation))
+            })
+            .map(|(mission, annotation)| sources.fingerprint(mission, annotation))
+            .collect::<Vec<_>>();
+        findings.sort();
+        Self {
+            version: BASELINE_VERSION,
+            findings,
+        }
+    }
+
+    /// Read a baseline file
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+        let baseline: Self = serde_json::from_str(&content)
+            .map_err(|e| format!("`{}` is invalid: {}", path.display(), e))?;
+        if baseline.version != BASELINE_VERSION {
+            return Err(format!(
+                "`{}` has version {}, expected {}",
+                path.display(),
+                baseline.version,
+                BASELINE_VERSION
+            ));
+        }
+        Ok(baseline)
+    }
+
+    /// Write the baseline file
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let content = serde_json::to_string_pretty(self).expect("baseline is serializable");
+        std::fs::write(path, content + "\n")
+            .map_err(|e| format!("failed to write {}: {}", path.display(), e))
+    }
+
+    /// Remove the known findings from a report, returning how many were hidden
+    ///
+    /// A finding is only hidden as many times as it is in the baseline, so a new copy of
+    /// a known finding is still reported
+    pub fn apply(&self, report: &mut Report) -> usize {
+        let mut known = HashMap::new();
+        for fingerprint in &self.findings {
+            *known.entry(fingerprint).or_insert(0_usize) += 1;
+        }
+        let mut sources = Sources::default();
+        let mut hidden = 0;
+        for mission in &mut report.missions {
+            let mut annotations = std::mem::take(&mut mission.annotations);
+            annotations.retain(|annotation| {
+                match known.get_mut(&sources.fingerprint(mission, annotation)) {
+                    Some(count) if *count > 0 => {
+                        *count -= 1;
+                        hidden += 1;
+                        false
+                    }
+                    _ => true,
+                }
+            });
+            mission.annotations = annotations;
+        }
+        hidden
+    }
+}
@@ -0,0 +1,51 @@
+use std::{path::PathBuf, process::Command};
+
+use crate::settings::Settings;
+
+/// Files changed since the merge base of `reference` and `HEAD`, relative to the working directory
+///
+/// Includes uncommitted and untracked files, only the local repository is read
+pub fn changed_files(reference: &str) -> Result<Vec<PathBuf>, String> {
+    let base = git(&["merge-base", reference, "HEAD"])?;
+    let base = base.trim();
+    let mut files = git(&["diff", "--name-only", "--relative", "-z", base])?
+        .split('\0')
+        .chain(git(&["ls-files", "--others", "--exclude-standard", "-z"])?.split('\0'))
+        .filter(|file| !file.is_empty())
+        .map(PathBuf::from)
+        .collect::<Vec<_>>();
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
+/// Keep the missions that contain a changed file
+///
+/// All missions are kept when a shared file changed
+pub fn select(settings: &Settings, missions: Vec<PathBuf>, changed: &[PathBuf]) -> Vec<PathBuf> {
+    if changed.iter().any(|file| settings.is_shared(file)) {
+        return missions;
+    }
+    missions
+        .into_iter()
+        .filter(|mission| {
+            let mission = mission.strip_prefix(".").unwrap_or(mission);
+            changed.iter().any(|file| file.starts_with(mission))
+        })
+        .collect()
+}
+
+fn git(args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .args(args)
+        .output()
+        .map_err(|e| format!("failed to run git: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    String::from_utf8(output.stdout).map_err(|e| format!("git output is not UTF-8: {}", e))
+}
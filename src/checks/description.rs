@@ -3,7 +3,7 @@ use std::path::Path;
 use hemtt_config::{Config, Property, Value};
 use hemtt_workspace::reporting::Processed;
 
-use crate::annotation::{Annotation, Level};
+use crate::{annotation::Annotation, rules};
 
 pub fn name_summary_author(dir: &Path, config: (&Processed, &Config)) -> Vec<Annotation> {
     let path = dir.to_path_buf().join("edit_me").join("description.ext");
@@ -25,7 +25,7 @@ pub fn name_summary_author(dir: &Path, config: (&Processed, &Config)) -> Vec<Ann
                             path.display().to_string(),
                             entry.span().clone(),
                             "OnLoadName is not set".to_string(),
-                            Level::Error,
+                            &rules::ON_LOAD_NAME,
                         ));
                     }
                 } else {
@@ -34,7 +34,7 @@ pub fn name_summary_author(dir: &Path, config: (&Processed, &Config)) -> Vec<Ann
                         path.display().to_string(),
                         entry.span().clone(),
                         "OnLoadName is not a string".to_string(),
-                        Level::Error,
+                        &rules::ON_LOAD_NAME,
                     ));
                 }
             }
@@ -47,7 +47,7 @@ pub fn name_summary_author(dir: &Path, config: (&Processed, &Config)) -> Vec<Ann
                             path.display().to_string(),
                             entry.span().clone(),
                             "OnLoadMission is not set".to_string(),
-                            Level::Error,
+                            &rules::ON_LOAD_MISSION,
                         ));
                     }
                     if summary.value().ends_with('.') {
@@ -56,7 +56,7 @@ pub fn name_summary_author(dir: &Path, config: (&Processed, &Config)) -> Vec<Ann
                             path.display().to_string(),
                             entry.span().clone(),
                             "OnLoadMission ends with a period".to_string(),
-                            Level::Warning,
+                            &rules::ON_LOAD_MISSION_STYLE,
                         ));
                     } else if summary.value().contains(". ") {
                         messages.push(Annotation::new(
@@ -64,7 +64,7 @@ pub fn name_summary_author(dir: &Path, config: (&Processed, &Config)) -> Vec<Ann
                             path.display().to_string(),
                             entry.span().clone(),
                             "OnLoadMission should be a single sentence".to_string(),
-                            Level::Warning,
+                            &rules::ON_LOAD_MISSION_STYLE,
                         ));
                     }
                 } else {
@@ -73,7 +73,7 @@ pub fn name_summary_author(dir: &Path, config: (&Processed, &Config)) -> Vec<Ann
                         path.display().to_string(),
                         entry.span().clone(),
                         "OnLoadMission is not a string".to_string(),
-                        Level::Error,
+                        &rules::ON_LOAD_MISSION,
                     ));
                 }
             }
@@ -86,7 +86,7 @@ pub fn name_summary_author(dir: &Path, config: (&Processed, &Config)) -> Vec<Ann
                             path.display().to_string(),
                             entry.span().clone(),
                             "author is not set".to_string(),
-                            Level::Error,
+                            &rules::AUTHOR,
                         ));
                     }
                 } else {
@@ -95,7 +95,7 @@ pub fn name_summary_author(dir: &Path, config: (&Processed, &Config)) -> Vec<Ann
                         path.display().to_string(),
                         entry.span().clone(),
                         "author is not a string".to_string(),
-                        Level::Error,
+                        &rules::AUTHOR,
                     ));
                 }
             }
@@ -107,7 +107,7 @@ pub fn name_summary_author(dir: &Path, config: (&Processed, &Config)) -> Vec<Ann
             path.display().to_string(),
             0..0,
             "OnLoadName is missing".to_string(),
-            Level::Error,
+            &rules::ON_LOAD_NAME,
         ));
     }
     if !found_summary {
@@ -116,7 +116,7 @@ pub fn name_summary_author(dir: &Path, config: (&Processed, &Config)) -> Vec<Ann
             path.display().to_string(),
             0..0,
             "OnLoadMission is missing".to_string(),
-            Level::Error,
+            &rules::ON_LOAD_MISSION,
         ));
     }
     if !found_author {
@@ -125,7 +125,7 @@ pub fn name_summary_author(dir: &Path, config: (&Processed, &Config)) -> Vec<Ann
             path.display().to_string(),
             0..0,
             "author is missing".to_string(),
-            Level::Error,
+            &rules::AUTHOR,
         ));
     }
     messages
@@ -7,19 +7,135 @@ use std::path::Path;
 use hemtt_config::{Class, Config, Property};
 use hemtt_workspace::reporting::Processed;
 
-use crate::{annotation::Annotation, get_class, get_string, GetChildren};
+use crate::{
+    annotation::Annotation,
+    get_class, get_number, get_string,
+    mission::{
+        model::{Connection, Entity, Mission},
+        spatial::SpatialIndex,
+    },
+    GetChildren,
+};
+
+#[derive(Debug, Clone, Copy)]
+/// A group or layer that contains an entity
+pub enum Ancestor<'a> {
+    Group {
+        id: Option<i32>,
+        side: Option<&'a str>,
+        class: &'a Class,
+    },
+    Layer {
+        name: Option<&'a str>,
+        class: &'a Class,
+    },
+}
+
+#[derive(Debug, Clone, Default)]
+/// The groups and layers around an entity, outermost first
+pub struct Context<'a> {
+    pub ancestors: Vec<Ancestor<'a>>,
+}
+
+impl<'a> Context<'a> {
+    /// The innermost group, as its ID and side
+    pub fn group(&self) -> Option<(Option<i32>, Option<&'a str>)> {
+        self.ancestors
+            .iter()
+            .rev()
+            .find_map(|ancestor| match ancestor {
+                Ancestor::Group { id, side, .. } => Some((*id, *side)),
+                Ancestor::Layer { .. } => None,
+            })
+    }
+
+    /// Names of the layers, outermost first
+    pub fn layers(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.ancestors.iter().filter_map(|ancestor| match ancestor {
+            Ancestor::Layer { name, .. } => *name,
+            Ancestor::Group { .. } => None,
+        })
+    }
+
+    /// The innermost layer class, to read any of its entries
+    pub fn layer(&self) -> Option<&'a Class> {
+        self.ancestors
+            .iter()
+            .rev()
+            .find_map(|ancestor| match ancestor {
+                Ancestor::Layer { class, .. } => Some(*class),
+                Ancestor::Group { .. } => None,
+            })
+    }
+}
 
 pub trait MissionCheck {
+    /// The rule ID of the check, one of [`crate::rules::RULES`]
+    fn rule(&self) -> &'static str;
     fn object(
         &mut self,
         _mission: (&Processed, &Config),
         _dir: &Path,
         _class: &Class,
         _data_type: &str,
+        _context: &Context,
+    ) {
+    }
+    /// Called for every group before its entities, the context does not include the group yet
+    fn enter_group(
+        &mut self,
+        _mission: (&Processed, &Config),
+        _dir: &Path,
+        _class: &Class,
+        _context: &Context,
+    ) {
+    }
+    /// Called after the entities of a group, also for a group without entities
+    fn leave_group(
+        &mut self,
+        _mission: (&Processed, &Config),
+        _dir: &Path,
+        _class: &Class,
+        _context: &Context,
+    ) {
+    }
+    /// Called for every layer before its entities, the context does not include the layer yet
+    fn enter_layer(
+        &mut self,
+        _mission: (&Processed, &Config),
+        _dir: &Path,
+        _class: &Class,
+        _context: &Context,
+    ) {
+    }
+    /// Called after the entities of a layer, also for a layer without entities
+    fn leave_layer(
+        &mut self,
+        _mission: (&Processed, &Config),
+        _dir: &Path,
+        _class: &Class,
+        _context: &Context,
     ) {
     }
     fn link(&mut self, _mission: (&Processed, &Config), _dir: &Path, _class: &Class) {}
-    fn done(&self, dir: &Path) -> Vec<Annotation>;
+    /// Called for every entity of the typed [`Mission`], after the raw entities
+    fn entity(&mut self, _mission: (&Processed, &Config), _dir: &Path, _entity: &Entity) {}
+    /// Called for every connection of the typed [`Mission`], after the entities
+    fn connection(
+        &mut self,
+        _mission: (&Processed, &Config),
+        _dir: &Path,
+        _model: &Mission,
+        _connection: &Connection,
+    ) {
+    }
+    /// Called once every entity has been seen, with an index of the entities by position
+    fn done(
+        &self,
+        mission: (&Processed, &Config),
+        dir: &Path,
+        index: &SpatialIndex,
+    ) -> Vec<Annotation>;
 }
 
 pub fn run_checks(
@@ -30,32 +146,72 @@ pub fn run_checks(
     let Some(entities) = get_class(mission.1, "Mission.Entities") else {
         return Vec::new();
     };
-    process_entities(mission, dir, &mut checks, entities);
+    process_entities(mission, dir, &mut checks, entities, &mut Context::default());
     if let Some(links) = get_class(mission.1, "Mission.Connections.Links") {
         process_links(mission, dir, &mut checks, links);
     }
-    checks.iter().flat_map(|c| c.done(dir)).collect()
+    let model = Mission::parse(mission.1);
+    for entity in &model.entities {
+        for check in &mut checks {
+            check.entity(mission, dir, entity);
+        }
+    }
+    for connection in &model.connections {
+        for check in &mut checks {
+            check.connection(mission, dir, &model, connection);
+        }
+    }
+    let index = SpatialIndex::new(&model);
+    checks
+        .iter()
+        .flat_map(|c| c.done(mission, dir, &index))
+        .collect()
 }
 
 fn process_entities<'a>(
-    mission: (&Processed, &Config),
+    mission: (&Processed, &'a Config),
     dir: &Path,
     checks: &mut Vec<Box<dyn MissionCheck>>,
     parent: &'a dyn GetChildren<'a>,
+    context: &mut Context<'a>,
 ) {
     for child in parent.get_children() {
         if let Property::Class(class) = child {
-            let Some((data_type, _)) = get_string(&class, "dataType") else {
+            let Some((data_type, _)) = get_string(class, "dataType") else {
                 continue;
             };
             for check in &mut *checks {
-                check.object(mission, dir, class, data_type);
+                check.object(mission, dir, class, data_type, context);
+            }
+            let ancestor = match data_type {
+                "Group" => Ancestor::Group {
+                    id: get_number(class, "id").map(|(id, _)| id),
+                    side: get_string(class, "side").map(|(side, _)| side),
+                    class,
+                },
+                "Layer" => Ancestor::Layer {
+                    name: get_string(class, "name").map(|(name, _)| name),
+                    class,
+                },
+                _ => continue,
+            };
+            for check in &mut *checks {
+                match ancestor {
+                    Ancestor::Group { .. } => check.enter_group(mission, dir, class, context),
+                    Ancestor::Layer { .. } => check.enter_layer(mission, dir, class, context),
+                }
+            }
+            // Empty groups and layers have no `Entities`, but are still entered and left
+            if let Some(entities) = get_class(class, "Entities") {
+                context.ancestors.push(ancestor);
+                process_entities(mission, dir, checks, entities, context);
+                context.ancestors.pop();
             }
-            if let "Group" | "Layer" = data_type {
-                let Some(entities) = get_class(&class, "Entities") else {
-                    continue;
-                };
-                process_entities(mission, dir, checks, entities);
+            for check in &mut *checks {
+                match ancestor {
+                    Ancestor::Group { .. } => check.leave_group(mission, dir, class, context),
+                    Ancestor::Layer { .. } => check.leave_layer(mission, dir, class, context),
+                }
             }
         }
     }
@@ -4,19 +4,33 @@ use hemtt_config::{Config, Item};
 use hemtt_workspace::reporting::Processed;
 
 use crate::{
-    annotation::{Annotation, Level},
-    checks::MissionCheck,
+    annotation::Annotation,
+    checks::{
+        objects::{hostiles::is_hostile, shops::is_shop, spawners::is_spawner},
+        Context, MissionCheck,
+    },
     extract_number, get_array, get_number, get_string,
+    mission::{
+        model::{Entity, Kind, Logic},
+        spatial::SpatialIndex,
+    },
+    rules,
 };
 
+const COVER_MAP: &str = "ModuleCoverMap_F";
+
 pub struct CoverMapCheck {
     messages: Vec<Annotation>,
+    required: bool,
+    independent_is_hostile: bool,
 }
 
 impl CoverMapCheck {
-    pub fn new() -> Self {
+    pub fn new(required: bool, resistance_west: i32) -> Self {
         Self {
             messages: Vec::new(),
+            required,
+            independent_is_hostile: resistance_west == 0,
         }
     }
 
@@ -36,19 +50,167 @@ impl CoverMapCheck {
     }
 }
 
-impl Default for CoverMapCheck {
-    fn default() -> Self {
-        Self::new()
+/// The area of a cover map, the module's trigger area
+struct Area {
+    center: (f32, f32),
+    /// Half of the width and length
+    size: (f32, f32),
+    /// Heading in radians, clockwise from north
+    heading: f32,
+    rectangle: bool,
+}
+
+impl Area {
+    fn new(entity: &Entity) -> Option<Self> {
+        let Kind::Logic(Logic {
+            area_is_rectangle,
+            area_size: Some([width, _, length]),
+        }) = &entity.kind
+        else {
+            return None;
+        };
+        Some(Self {
+            center: entity.position_2d()?,
+            size: (width.abs(), length.abs()),
+            heading: entity.rotation.map_or(0.0, |[_, heading, _]| heading),
+            rectangle: *area_is_rectangle,
+        })
+    }
+
+    fn contains(&self, position: (f32, f32)) -> bool {
+        let (dx, dy) = (position.0 - self.center.0, position.1 - self.center.1);
+        let (sin, cos) = self.heading.sin_cos();
+        let x = dx * cos - dy * sin;
+        let y = dx * sin + dy * cos;
+        if self.rectangle {
+            x.abs() <= self.size.0 && y.abs() <= self.size.1
+        } else {
+            (x / self.size.0).powi(2) + (y / self.size.1).powi(2) <= 1.0
+        }
+    }
+}
+
+fn is_cover_map(entity: &Entity) -> bool {
+    matches!(entity.kind, Kind::Logic(_))
+        && entity
+            .class_type
+            .as_ref()
+            .is_some_and(|class_type| class_type.value == COVER_MAP)
+}
+
+impl CoverMapCheck {
+    /// Cover map count and the entities outside of the cover maps
+    fn areas(&self, processed: &Processed, dir: &Path, index: &SpatialIndex) -> Vec<Annotation> {
+        let path = dir.join("mission.sqm").display().to_string();
+        let mission = index.mission();
+        let mut messages = Vec::new();
+        let cover_maps = mission
+            .entities
+            .iter()
+            .filter(|entity| is_cover_map(entity))
+            .collect::<Vec<_>>();
+        let Some(first) = cover_maps.first() else {
+            if self.required {
+                messages.push(Annotation::new(
+                    None,
+                    path,
+                    0..0,
+                    "No cover map found, contracts need one over the area of operations"
+                        .to_string(),
+                    &rules::COVER_MAP_COUNT,
+                ));
+            }
+            return messages;
+        };
+        for extra in &cover_maps[1..] {
+            messages.push(
+                Annotation::new(
+                    Some(processed),
+                    path.clone(),
+                    extra.type_span(),
+                    format!("{} cover maps found, only one is allowed", cover_maps.len()),
+                    &rules::COVER_MAP_COUNT,
+                )
+                .with_related(
+                    Some(processed),
+                    first.type_span(),
+                    "First cover map".to_string(),
+                ),
+            );
+        }
+        let areas = cover_maps
+            .iter()
+            .filter_map(|entity| Area::new(entity))
+            .collect::<Vec<_>>();
+        if areas.is_empty() {
+            return messages;
+        }
+        let outside = |entity: &Entity| {
+            entity
+                .position_2d()
+                .is_some_and(|position| !areas.iter().any(|area| area.contains(position)))
+        };
+        for entity in &mission.entities {
+            let what = match &entity.kind {
+                Kind::Group(group) => {
+                    let members = group
+                        .units
+                        .iter()
+                        .filter_map(|id| mission.entity(*id))
+                        .collect::<Vec<_>>();
+                    let what = if members.iter().any(|member| member.is_playable()) {
+                        "Playable units of a group are"
+                    } else if is_hostile(
+                        entity.side.as_ref().map(|side| side.value.as_str()),
+                        self.independent_is_hostile,
+                    ) {
+                        "Units of a hostile group are"
+                    } else {
+                        continue;
+                    };
+                    // Report the group once, at its first unit outside of the cover map
+                    let Some(member) = members.into_iter().find(|member| outside(member)) else {
+                        continue;
+                    };
+                    messages.push(Annotation::new(
+                        Some(processed),
+                        path.clone(),
+                        member.type_span(),
+                        format!("{} outside of the cover map", what),
+                        &rules::COVER_MAP_OUTSIDE,
+                    ));
+                    continue;
+                }
+                _ if is_shop(entity) => "Shop is",
+                _ if is_spawner(entity) => "Spawner is",
+                _ => continue,
+            };
+            if outside(entity) {
+                messages.push(Annotation::new(
+                    Some(processed),
+                    path.clone(),
+                    entity.type_span(),
+                    format!("{} outside of the cover map", what),
+                    &rules::COVER_MAP_OUTSIDE,
+                ));
+            }
+        }
+        messages
     }
 }
 
 impl MissionCheck for CoverMapCheck {
+    fn rule(&self) -> &'static str {
+        "cover-map"
+    }
+
     fn object(
         &mut self,
         _: (&Processed, &Config),
         dir: &Path,
         class: &hemtt_config::Class,
         data_type: &str,
+        _: &Context,
     ) {
         if data_type != "Logic" {
             return;
@@ -58,7 +220,7 @@ impl MissionCheck for CoverMapCheck {
             return;
         };
 
-        if class_type != "ModuleCoverMap_F" {
+        if class_type != COVER_MAP {
             return;
         }
 
@@ -70,7 +232,7 @@ impl MissionCheck for CoverMapCheck {
                 dir.join("mission.sqm").display().to_string(),
                 0..0,
                 "Cover maps must be rectangles (set areaIsRectangle=1)".to_string(),
-                Level::Error,
+                &rules::COVER_MAP_SHAPE,
             ));
             return;
         }
@@ -86,13 +248,20 @@ impl MissionCheck for CoverMapCheck {
                         "Cover map dimensions must be a multiple of 100 (current: {:.2} × {:.2})",
                         width, length
                     ),
-                    Level::Error,
+                    &rules::COVER_MAP_SIZE,
                 ));
             }
         }
     }
 
-    fn done(&self, _: &Path) -> Vec<Annotation> {
-        self.messages.clone()
+    fn done(
+        &self,
+        mission: (&Processed, &Config),
+        dir: &Path,
+        index: &SpatialIndex,
+    ) -> Vec<Annotation> {
+        let mut messages = self.messages.clone();
+        messages.extend(self.areas(mission.0, dir, index));
+        messages
     }
 }
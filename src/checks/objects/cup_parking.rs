@@ -4,9 +4,11 @@ use hemtt_config::Config;
 use hemtt_workspace::reporting::Processed;
 
 use crate::{
-    annotation::{Annotation, Level},
-    checks::MissionCheck,
+    annotation::Annotation,
+    checks::{Context, MissionCheck},
     get_string,
+    mission::spatial::SpatialIndex,
+    rules,
 };
 
 pub struct CUPParking {
@@ -26,12 +28,17 @@ impl Default for CUPParking {
 }
 
 impl MissionCheck for CUPParking {
+    fn rule(&self) -> &'static str {
+        "cup-parking"
+    }
+
     fn object(
         &mut self,
         _: (&Processed, &Config),
         _: &Path,
         class: &hemtt_config::Class,
         data_type: &str,
+        _: &Context,
     ) {
         if data_type != "Object" {
             return;
@@ -47,14 +54,14 @@ impl MissionCheck for CUPParking {
         }
     }
 
-    fn done(&self, dir: &Path) -> Vec<Annotation> {
+    fn done(&self, _: (&Processed, &Config), dir: &Path, _: &SpatialIndex) -> Vec<Annotation> {
         if self.seen {
             vec![Annotation::new(
                 None,
                 dir.join("mission.sqm").display().to_string(),
                 0..0,
                 "Use the vanilla \"Parking Lot\" (RoadSign_Livonia_parking) sign, the CUP sign floats above the ground".to_string(),
-                Level::Error,
+                &rules::CUP_PARKING,
             )]
         } else {
             vec![]
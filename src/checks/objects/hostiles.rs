@@ -4,63 +4,132 @@ use hemtt_config::Config;
 use hemtt_workspace::reporting::Processed;
 
 use crate::{
-    annotation::{Annotation, Level},
-    checks::{objects::hostiles, MissionCheck},
-    get_class, get_number, get_string,
+    annotation::Annotation,
+    checks::MissionCheck,
+    mission::{
+        model::{Entity, Group, Kind},
+        spatial::SpatialIndex,
+    },
+    rules,
 };
 
 pub struct HostilesCheck {
     east: u32,
     independent: u32,
     independent_is_hostile: bool,
+    min_distance: f32,
 }
 
 impl HostilesCheck {
-    pub fn new(resistance_west: i32) -> Self {
+    pub fn new(resistance_west: i32, min_distance: f32) -> Self {
         Self {
             east: 0,
             independent: 0,
             independent_is_hostile: resistance_west == 0,
+            min_distance,
         }
     }
-}
 
-impl MissionCheck for HostilesCheck {
-    fn object(
-        &mut self,
-        mission: (&Processed, &Config),
+    /// Hostile group leaders and crewed vehicles that are close to a playable unit
+    fn near_players(
+        &self,
+        processed: &Processed,
         dir: &Path,
-        class: &hemtt_config::Class,
-        data_type: &str,
-    ) {
-        if data_type != "Object" {
-            return;
-        }
-        let Some(attributes) = get_class(class, "Attributes") else {
-            return;
-        };
-        let is_player = get_number(&attributes, "isPlayer")
-            .map(|(value, _)| value)
-            .unwrap_or(0);
-        let is_playable = get_number(&attributes, "isPlayable")
-            .map(|(value, _)| value)
-            .unwrap_or(0);
-        if is_player == 1 || is_playable == 1 {
-            return;
+        index: &SpatialIndex,
+    ) -> Vec<Annotation> {
+        let mission = index.mission();
+        let mut messages = Vec::new();
+        for group in &mission.entities {
+            let Kind::Group(Group { units, .. }) = &group.kind else {
+                continue;
+            };
+            if !is_hostile(
+                group.side.as_ref().map(|side| side.value.as_str()),
+                self.independent_is_hostile,
+            ) {
+                continue;
+            }
+            // The first unit of a group is its leader
+            let hostiles = units
+                .iter()
+                .filter_map(|id| mission.entity(*id))
+                .enumerate()
+                .filter(|(i, member)| {
+                    *i == 0 || matches!(&member.kind, Kind::Object(object) if object.crew)
+                })
+                .map(|(_, member)| member);
+            for hostile in hostiles {
+                let Some(position) = hostile.position_2d() else {
+                    continue;
+                };
+                let Some((player, distance)) = index.nearest(position, Entity::is_playable) else {
+                    return messages;
+                };
+                if distance >= self.min_distance {
+                    continue;
+                }
+                let name = hostile
+                    .class_type
+                    .as_ref()
+                    .map_or("Hostile", |class_type| class_type.value.as_str());
+                messages.push(
+                    Annotation::new(
+                        Some(processed),
+                        dir.join("mission.sqm").display().to_string(),
+                        hostile.type_span(),
+                        format!(
+                            "{} ({}) is {:.0}m from the nearest playable unit, at least {:.0}m is required",
+                            name,
+                            group.side.as_ref().map_or("", |side| side.value.as_str()),
+                            distance,
+                            self.min_distance
+                        ),
+                        &rules::HOSTILES_NEAR_PLAYERS,
+                    )
+                    .with_related(
+                        Some(processed),
+                        player.type_span(),
+                        "Nearest playable unit".to_string(),
+                    ),
+                );
+            }
         }
+        messages
+    }
+}
+
+/// Check if a side is hostile to the players, Independent is hostile unless it is friendly to West
+pub fn is_hostile(side: Option<&str>, independent_is_hostile: bool) -> bool {
+    match side {
+        Some("East") => true,
+        Some("Independent") => independent_is_hostile,
+        _ => false,
+    }
+}
 
-        let Some((side, side_range)) = get_string(class, "side") else {
+impl MissionCheck for HostilesCheck {
+    fn rule(&self) -> &'static str {
+        "hostiles"
+    }
+
+    fn entity(&mut self, _: (&Processed, &Config), _: &Path, entity: &Entity) {
+        if !matches!(entity.kind, Kind::Object(_)) || entity.is_playable() {
             return;
-        };
-        if side == "East" {
-            self.east += 1;
-        } else if side == "Independent" {
-            self.independent += 1;
+        }
+        match entity.side.as_ref().map(|side| side.value.as_str()) {
+            Some("East") => self.east += 1,
+            Some("Independent") => self.independent += 1,
+            _ => {}
         }
     }
 
-    fn done(&self, dir: &Path) -> Vec<Annotation> {
-        let mut messages = Vec::new();
+    fn done(
+        &self,
+        mission: (&Processed, &Config),
+        dir: &Path,
+        index: &SpatialIndex,
+    ) -> Vec<Annotation> {
+        let mut messages = self.near_players(mission.0, dir, index);
         let hostiles = self.east
             + if self.independent_is_hostile {
                 self.independent
@@ -76,7 +145,7 @@ impl MissionCheck for HostilesCheck {
                     "No hostiles found in mission (East: {}, Independent: {})",
                     self.east, self.independent
                 ),
-                Level::Error,
+                &rules::NO_HOSTILES,
             ));
         }
         messages
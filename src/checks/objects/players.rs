@@ -4,9 +4,11 @@ use hemtt_config::Config;
 use hemtt_workspace::reporting::Processed;
 
 use crate::{
-    annotation::{Annotation, Level},
-    checks::MissionCheck,
+    annotation::Annotation,
+    checks::{Context, MissionCheck},
     get_class, get_number, get_string,
+    mission::spatial::SpatialIndex,
+    rules,
 };
 
 pub struct PlayerCheck {
@@ -46,12 +48,17 @@ impl PlayerCheck {
 }
 
 impl MissionCheck for PlayerCheck {
+    fn rule(&self) -> &'static str {
+        "players"
+    }
+
     fn object(
         &mut self,
         mission: (&Processed, &Config),
         dir: &Path,
         class: &hemtt_config::Class,
         data_type: &str,
+        _: &Context,
     ) {
         if data_type != "Object" {
             return;
@@ -83,7 +90,7 @@ impl MissionCheck for PlayerCheck {
                 dir.join("mission.sqm").display().to_string(),
                 side_range.clone(),
                 "Player side should be 'West'".to_string(),
-                Level::Error,
+                &rules::PLAYER_SIDE,
             ));
             return;
         }
@@ -95,7 +102,7 @@ impl MissionCheck for PlayerCheck {
                     dir.join("mission.sqm").display().to_string(),
                     attributes.name().map(|n| n.span.clone()).unwrap_or(0..0),
                     "All player descriptions should be 'Contractor'".to_string(),
-                    Level::Error,
+                    &rules::PLAYER_DESCRIPTION,
                 ));
                 self.did_log_player_description = true;
             }
@@ -107,7 +114,7 @@ impl MissionCheck for PlayerCheck {
                 dir.join("mission.sqm").display().to_string(),
                 description_span.clone(),
                 "All player descriptions should be 'Contractor'".to_string(),
-                Level::Error,
+                &rules::PLAYER_DESCRIPTION,
             ));
             self.did_log_player_description = true;
         }
@@ -121,12 +128,12 @@ impl MissionCheck for PlayerCheck {
                 dir.join("mission.sqm").display().to_string(),
                 class_span.clone(),
                 "Player class should be 'synixe_factions_synixe_Contractor'".to_string(),
-                Level::Error,
+                &rules::PLAYER_CLASS,
             ));
         }
     }
 
-    fn done(&self, dir: &Path) -> Vec<Annotation> {
+    fn done(&self, _: (&Processed, &Config), dir: &Path, _: &SpatialIndex) -> Vec<Annotation> {
         let mut messages = self.messages.clone();
         if self.count != self.expected {
             messages.push(Annotation::new(
@@ -134,7 +141,7 @@ impl MissionCheck for PlayerCheck {
                 dir.join("mission.sqm").display().to_string(),
                 0..0,
                 format!("Expected {} players, found {}", self.expected, self.count),
-                Level::Error,
+                &rules::PLAYER_COUNT,
             ));
         }
         messages
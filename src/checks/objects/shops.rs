@@ -4,117 +4,173 @@ use hemtt_config::Config;
 use hemtt_workspace::reporting::Processed;
 
 use crate::{
-    annotation::{Annotation, Level},
+    annotation::Annotation,
     checks::MissionCheck,
-    get_class, get_number, get_string, GetChildren,
+    fix::Fix,
+    mission::{
+        model::{AttributeValue, Entity, Kind},
+        spatial::SpatialIndex,
+    },
+    rules::{self, Code},
 };
 
-const REQUIRED_SHOPS: usize = 2;
+/// Custom attribute that makes an object a shop
+pub const SHOP_ATTRIBUTE: &str = "crate_client_gear_attribute_shop";
+
+/// Check if an entity is a shop, an object with the shop attribute ticked
+pub fn is_shop(entity: &Entity) -> bool {
+    matches!(entity.kind, Kind::Object(_))
+        && entity
+            .custom_attribute(SHOP_ATTRIBUTE)
+            .and_then(AttributeValue::as_int)
+            == Some(1)
+}
 
 pub struct ShopCheck {
     messages: Vec<Annotation>,
     count: usize,
+    required: usize,
+    max_distance: f32,
 }
 
 impl ShopCheck {
-    pub fn new() -> Self {
+    pub fn new(required: usize, max_distance: f32) -> Self {
         Self {
             messages: Vec::new(),
             count: 0,
+            required,
+            max_distance,
         }
     }
 
-    fn check_attribute<'a>(
+    /// Report an attribute of a shop that is set to 1, the fix sets it to 0
+    fn check_attribute(
         &mut self,
+        processed: &Processed,
         dir: &Path,
-        attributes: &'a dyn GetChildren<'a>,
+        entity: &Entity,
         property: &str,
-        message: String,
+        message: &str,
+        code: &'static Code,
     ) {
-        if let Some((value, span)) = get_number(attributes, property) {
-            if value == 1 {
-                self.messages.push(Annotation::new(
-                    None,
-                    dir.join("mission.sqm").display().to_string(),
-                    span,
-                    message,
-                    Level::Error,
-                ));
-            }
+        let Some(attribute) = entity.attributes.get(property) else {
+            return;
+        };
+        if attribute.value.as_int() != Some(1) {
+            return;
         }
+        let fix = Fix::replace(Some(processed), &attribute.span, "0");
+        self.messages.push(
+            Annotation::new(
+                Some(processed),
+                dir.join("mission.sqm").display().to_string(),
+                attribute.span.clone(),
+                message.to_string(),
+                code,
+            )
+            .with_fix(fix),
+        );
     }
-}
 
-impl Default for ShopCheck {
-    fn default() -> Self {
-        Self::new()
+    /// Shops that are far from every playable unit
+    fn distances(
+        &self,
+        processed: &Processed,
+        dir: &Path,
+        index: &SpatialIndex,
+    ) -> Vec<Annotation> {
+        let mut messages = Vec::new();
+        for (shop, position) in index.positioned().filter(|(entity, _)| is_shop(entity)) {
+            let Some((player, distance)) = index.nearest(position, Entity::is_playable) else {
+                break;
+            };
+            if distance <= self.max_distance {
+                continue;
+            }
+            messages.push(
+                Annotation::new(
+                    Some(processed),
+                    dir.join("mission.sqm").display().to_string(),
+                    shop.type_span(),
+                    format!(
+                        "Shop is {:.0}m from the nearest playable unit, it must be within {:.0}m of the player start",
+                        distance, self.max_distance
+                    ),
+                    &rules::SHOP_DISTANCE,
+                )
+                .with_related(
+                    Some(processed),
+                    player.type_span(),
+                    "Nearest playable unit".to_string(),
+                ),
+            );
+        }
+        messages
     }
 }
 
 impl MissionCheck for ShopCheck {
-    fn object(
-        &mut self,
-        _: (&Processed, &Config),
-        dir: &Path,
-        class: &hemtt_config::Class,
-        data_type: &str,
-    ) {
-        if data_type != "Object" {
-            return;
-        }
-        let Some(custom_attributes) = get_class(class, "CustomAttributes") else {
+    fn rule(&self) -> &'static str {
+        "shops"
+    }
+
+    fn entity(&mut self, mission: (&Processed, &Config), dir: &Path, entity: &Entity) {
+        if !is_shop(entity) {
             return;
-        };
-        for attribute in custom_attributes.get_children() {
-            let Some((property, _)) = get_string(attribute, "property") else {
-                continue;
-            };
-            if property == "crate_client_gear_attribute_shop" {
-                if let Some(attributes) = get_class(class, "Attributes") {
-                    self.check_attribute(
-                        dir,
-                        &attributes,
-                        "createAsSimpleObject",
-                        "shops must not be simple objects".to_string(),
-                    );
-                    self.check_attribute(
-                        dir,
-                        &attributes,
-                        "createAsSimpleObject",
-                        "shops must not be simple objects".to_string(),
-                    );
-                    // self.check_attribute(
-                    //     dir,
-                    //     &attributes,
-                    //     "simulation",
-                    //     "shops must not have simulation disabled".to_string(),
-                    // );
-                }
-                self.count += 1;
-            }
         }
+        self.check_attribute(
+            mission.0,
+            dir,
+            entity,
+            "createAsSimpleObject",
+            "shops must not be simple objects",
+            &rules::SIMPLE_OBJECT_SHOP,
+        );
+        self.check_attribute(
+            mission.0,
+            dir,
+            entity,
+            "disableSimulation",
+            "shops must not have simulation disabled",
+            &rules::SHOP_SIMULATION,
+        );
+        self.check_attribute(
+            mission.0,
+            dir,
+            entity,
+            "hideObject",
+            "shops must not be hidden",
+            &rules::SHOP_HIDDEN,
+        );
+        self.count += 1;
     }
 
-    fn done(&self, dir: &Path) -> Vec<Annotation> {
+    fn done(
+        &self,
+        mission: (&Processed, &Config),
+        dir: &Path,
+        index: &SpatialIndex,
+    ) -> Vec<Annotation> {
         let mut messages = self.messages.clone();
-        if self.count == 0 {
+        messages.extend(self.distances(mission.0, dir, index));
+        if self.count == 0 && self.required > 0 {
             messages.push(Annotation::new(
                 None,
                 dir.join("mission.sqm").display().to_string(),
                 0..0,
                 "No shops found".to_string(),
-                Level::Error,
+                &rules::SHOP_COUNT,
             ));
-        } else if self.count < REQUIRED_SHOPS {
+        } else if self.count < self.required {
             messages.push(Annotation::new(
                 None,
                 dir.join("mission.sqm").display().to_string(),
                 0..0,
                 format!(
                     "Not enough shops found, at least {} are required",
-                    REQUIRED_SHOPS
+                    self.required
                 ),
-                Level::Error,
+                &rules::SHOP_COUNT,
             ));
         }
         messages
@@ -4,11 +4,18 @@ use hemtt_config::Config;
 use hemtt_workspace::reporting::Processed;
 
 use crate::{
-    annotation::{Annotation, Level},
+    annotation::Annotation,
     checks::MissionCheck,
-    get_string,
+    mission::{
+        model::{Entity, Kind},
+        spatial::{distance, SpatialIndex},
+    },
+    rules,
 };
 
+/// Prefix of the spawner objects, followed by `<kind>_<size>`
+const SPAWNER_PREFIX: &str = "crate_client_garage_";
+
 pub struct SpawnersCheck {
     count_land: u32,
     count_air: u32,
@@ -37,24 +44,185 @@ impl SpawnersCheck {
     }
 }
 
+/// Kind, size and radius of a spawner object
+pub fn spawner(entity: &Entity) -> Option<(&str, &str, f32)> {
+    if !matches!(entity.kind, Kind::Object(_)) {
+        return None;
+    }
+    let (kind, size) = entity
+        .class_type
+        .as_ref()?
+        .value
+        .strip_prefix(SPAWNER_PREFIX)?
+        .split_once('_')?;
+    Some((kind, size, radius(kind, size)?))
+}
+
+/// Check if an entity is a spawner, an object or a marker in older templates
+pub fn is_spawner(entity: &Entity) -> bool {
+    match &entity.kind {
+        Kind::Object(_) => spawner(entity).is_some(),
+        Kind::Marker(marker) => marker.name.as_ref().is_some_and(|name| {
+            matches!(
+                name.value.as_str(),
+                "spawn_land" | "spawn_air" | "spawn_sea" | "spawn_thing"
+            )
+        }),
+        _ => false,
+    }
+}
+
+/// The class and extent of a wall, barrier or container that vehicles can collide with
+///
+/// Units and crewed vehicles are in groups, hidden objects have no collision and other objects
+/// like signs and lights are small enough to be pushed away
+fn obstacle(entity: &Entity) -> Option<(&str, f32)> {
+    if !matches!(entity.kind, Kind::Object(_))
+        || entity.group.is_some()
+        || entity
+            .attributes
+            .get("hideObject")
+            .and_then(|value| value.value.as_int())
+            == Some(1)
+    {
+        return None;
+    }
+    let class_type = entity.class_type.as_ref()?.value.as_str();
+    Some((class_type, extent(class_type)?))
+}
+
+/// Overlapping and obstructed spawners
+fn footprints(processed: &Processed, dir: &Path, index: &SpatialIndex) -> Vec<Annotation> {
+    let path = dir.join("mission.sqm").display().to_string();
+    let mut messages = Vec::new();
+    let spawners = index
+        .positioned()
+        .filter_map(|(entity, position)| Some((entity, position, spawner(entity)?)))
+        .collect::<Vec<_>>();
+    for (i, (entity, position, (kind, size, radius))) in spawners.iter().enumerate() {
+        for (other, other_position, (other_kind, other_size, other_radius)) in &spawners[i + 1..] {
+            let distance = distance(*position, *other_position);
+            if distance >= radius + other_radius {
+                continue;
+            }
+            messages.push(
+                Annotation::new(
+                    Some(processed),
+                    path.clone(),
+                    entity.type_span(),
+                    format!(
+                        "{} {} spawner overlaps {} {} spawner, {:.1}m apart but {:.1}m needed",
+                        size,
+                        kind,
+                        other_size,
+                        other_kind,
+                        distance,
+                        radius + other_radius
+                    ),
+                    &rules::SPAWNER_OVERLAP,
+                )
+                .with_related(
+                    Some(processed),
+                    other.type_span(),
+                    format!("{} {} spawner", other_size, other_kind),
+                ),
+            );
+        }
+        for (other, distance) in index.within(*position, radius + MAX_EXTENT) {
+            let Some((class_type, extent)) = obstacle(other) else {
+                continue;
+            };
+            if distance >= radius + extent {
+                continue;
+            }
+            messages.push(
+                Annotation::new(
+                    Some(processed),
+                    path.clone(),
+                    entity.type_span(),
+                    format!(
+                        "{} is {:.1}m from the center of a {} {} spawner, it reaches about {:.1}m into its {:.1}m radius",
+                        class_type,
+                        distance,
+                        size,
+                        kind,
+                        radius + extent - distance,
+                        radius
+                    ),
+                    &rules::SPAWNER_OBSTRUCTED,
+                )
+                .with_related(
+                    Some(processed),
+                    other.type_span(),
+                    format!("{} is placed here", class_type),
+                ),
+            );
+        }
+    }
+    messages
+}
+
+/// Largest value of [`extent`]
+const MAX_EXTENT: f32 = 6.5;
+
+/// How far a wall, barrier or container reaches from its position, in meters, half of its
+/// longest side
+///
+/// The sizes are approximations by the kind of object in the class name, `None` for other
+/// objects
+fn extent(class_type: &str) -> Option<f32> {
+    let class_type = class_type.to_ascii_lowercase();
+    // More specific names first, the first match is used
+    let extents: [(&str, f32); 10] = [
+        ("cargo40", 6.5),
+        ("container", 6.5),
+        ("cargo20", 3.5),
+        ("hbarrier_big", 4.5),
+        ("hbarrierwall", 4.5),
+        ("hbarrier", 3.0),
+        ("bagfence", 1.5),
+        ("wall", 4.0),
+        ("fence", 4.0),
+        ("barrier", 2.0),
+    ];
+    extents
+        .iter()
+        .find(|(name, _)| class_type.contains(name))
+        .map(|(_, extent)| *extent)
+}
+
+/// Radius of the area a spawner places vehicles in, in meters
+const fn radius(kind: &str, size: &str) -> Option<f32> {
+    let base = match kind.as_bytes() {
+        b"land" => 4.0,
+        b"air" => 8.0,
+        b"sea" => 6.0,
+        b"thing" => 1.5,
+        _ => return None,
+    };
+    Some(match size.as_bytes() {
+        b"small" => base,
+        b"medium" => base * 1.5,
+        b"large" => base * 2.0,
+        _ => return None,
+    })
+}
+
 impl MissionCheck for SpawnersCheck {
-    fn object(
-        &mut self,
-        _: (&Processed, &Config),
-        _: &Path,
-        class: &hemtt_config::Class,
-        data_type: &str,
-    ) {
-        if data_type != self.data_type {
+    fn rule(&self) -> &'static str {
+        "spawners"
+    }
+
+    fn entity(&mut self, _: (&Processed, &Config), _: &Path, entity: &Entity) {
+        if entity.data_type != self.data_type {
             return;
         }
-        match data_type {
-            "Object" => {
-                let Some((type_, _)) = get_string(class, "type") else {
+        match &entity.kind {
+            Kind::Object(_) => {
+                let Some(type_) = &entity.class_type else {
                     return;
                 };
-                // TODO check distance between spawners
-                match type_ {
+                match type_.value.as_str() {
                     "crate_client_garage_land_large"
                     | "crate_client_garage_land_medium"
                     | "crate_client_garage_land_small" => self.count_land += 1,
@@ -70,11 +238,11 @@ impl MissionCheck for SpawnersCheck {
                     _ => {}
                 }
             }
-            "Marker" => {
-                let Some((name, _)) = get_string(class, "name") else {
+            Kind::Marker(marker) => {
+                let Some(name) = &marker.name else {
                     return;
                 };
-                match name {
+                match name.value.as_str() {
                     "spawn_land" => self.count_land += 1,
                     "spawn_air" => self.count_air += 1,
                     "spawn_sea" => self.count_sea += 1,
@@ -86,8 +254,13 @@ impl MissionCheck for SpawnersCheck {
         }
     }
 
-    fn done(&self, dir: &Path) -> Vec<Annotation> {
-        let mut messages = vec![];
+    fn done(
+        &self,
+        mission: (&Processed, &Config),
+        dir: &Path,
+        index: &SpatialIndex,
+    ) -> Vec<Annotation> {
+        let mut messages = footprints(mission.0, dir, index);
         if self.expected {
             if self.count_land == 0 && !self.acknowledged_land {
                 messages.push(Annotation::new(
@@ -95,7 +268,7 @@ impl MissionCheck for SpawnersCheck {
                     dir.join("mission.sqm").display().to_string(),
                     0..0,
                     "No land spawner found".to_string(),
-                    Level::Error,
+                    &rules::MISSING_SPAWNER,
                 ));
             }
             if self.count_thing == 0 && !self.acknowledged_land {
@@ -104,7 +277,7 @@ impl MissionCheck for SpawnersCheck {
                     dir.join("mission.sqm").display().to_string(),
                     0..0,
                     "No thing spawner found".to_string(),
-                    Level::Error,
+                    &rules::MISSING_SPAWNER,
                 ));
             }
         } else {
@@ -115,7 +288,7 @@ impl MissionCheck for SpawnersCheck {
                     0..0,
                     "Land spawner found, but spawners are not allowed on this mission type"
                         .to_string(),
-                    Level::Error,
+                    &rules::UNEXPECTED_SPAWNER,
                 ));
             }
             if self.count_air != 0 {
@@ -125,7 +298,7 @@ impl MissionCheck for SpawnersCheck {
                     0..0,
                     "Air spawner found, but spawners are not allowed on this mission type"
                         .to_string(),
-                    Level::Error,
+                    &rules::UNEXPECTED_SPAWNER,
                 ));
             }
             if self.count_sea != 0 {
@@ -135,7 +308,7 @@ impl MissionCheck for SpawnersCheck {
                     0..0,
                     "Sea spawner found, but spawners are not allowed on this mission type"
                         .to_string(),
-                    Level::Error,
+                    &rules::UNEXPECTED_SPAWNER,
                 ));
             }
             if self.count_thing != 0 {
@@ -145,7 +318,7 @@ impl MissionCheck for SpawnersCheck {
                     0..0,
                     "Thing spawner found, but spawners are not allowed on this mission type"
                         .to_string(),
-                    Level::Error,
+                    &rules::UNEXPECTED_SPAWNER,
                 ));
             }
         }
@@ -4,9 +4,11 @@ use hemtt_config::Config;
 use hemtt_workspace::reporting::Processed;
 
 use crate::{
-    annotation::{Annotation, Level},
-    checks::MissionCheck,
+    annotation::Annotation,
+    checks::{Context, MissionCheck},
     get_string,
+    mission::spatial::SpatialIndex,
+    rules,
 };
 
 pub struct RequireSpectator {
@@ -26,12 +28,17 @@ impl Default for RequireSpectator {
 }
 
 impl MissionCheck for RequireSpectator {
+    fn rule(&self) -> &'static str {
+        "spectator"
+    }
+
     fn object(
         &mut self,
         _: (&Processed, &Config),
         _: &Path,
         class: &hemtt_config::Class,
         data_type: &str,
+        _: &Context,
     ) {
         if data_type != "Object" {
             return;
@@ -47,14 +54,14 @@ impl MissionCheck for RequireSpectator {
         }
     }
 
-    fn done(&self, dir: &Path) -> Vec<Annotation> {
+    fn done(&self, _: (&Processed, &Config), dir: &Path, _: &SpatialIndex) -> Vec<Annotation> {
         if !self.seen {
             vec![Annotation::new(
                 None,
                 dir.join("mission.sqm").display().to_string(),
                 0..0,
                 "No spectator screen found".to_string(),
-                Level::Error,
+                &rules::SPECTATOR_SCREEN,
             )]
         } else {
             vec![]
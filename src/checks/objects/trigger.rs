@@ -4,9 +4,12 @@ use hemtt_config::{Class, Config, Ident};
 use hemtt_workspace::reporting::Processed;
 
 use crate::{
-    annotation::{Annotation, Level},
-    checks::MissionCheck,
+    annotation::Annotation,
+    checks::{Context, MissionCheck},
+    fix::Fix,
     get_class, get_float, get_number, get_string,
+    mission::spatial::SpatialIndex,
+    rules, GetChildren,
 };
 
 pub struct TriggerChecks {
@@ -34,12 +37,17 @@ impl Default for TriggerChecks {
 }
 
 impl MissionCheck for TriggerChecks {
+    fn rule(&self) -> &'static str {
+        "triggers"
+    }
+
     fn object(
         &mut self,
         _: (&Processed, &Config),
         dir: &Path,
         class: &hemtt_config::Class,
         data_type: &str,
+        _: &Context,
     ) {
         match data_type {
             "Trigger" => {
@@ -56,7 +64,7 @@ impl MissionCheck for TriggerChecks {
                         dir.join("mission.sqm").display().to_string(),
                         0..0,
                         "Guard waypoint is not allowed".to_string(),
-                        Level::Error,
+                        &rules::GUARD_WAYPOINT,
                     ));
                 }
             }
@@ -64,7 +72,7 @@ impl MissionCheck for TriggerChecks {
         }
     }
 
-    fn link(&mut self, _mission: (&Processed, &Config), dir: &Path, class: &Class) {
+    fn link(&mut self, mission: (&Processed, &Config), dir: &Path, class: &Class) {
         let Some(custom_data) = get_class(class, "CustomData") else {
             return;
         };
@@ -135,7 +143,7 @@ impl MissionCheck for TriggerChecks {
                             dir.join("mission.sqm").display().to_string(),
                             0..0,
                             format!("WaypointActivation link has types {} and {}, which is not an allowed pair", waypoint_type_0, waypoint_type_1),
-                            Level::Error,
+                            &rules::WAYPOINT_PAIR,
                         ));
                     }
                     return;
@@ -152,46 +160,79 @@ impl MissionCheck for TriggerChecks {
             let Some(attributes) = get_class(trigger, "Attributes") else {
                 return;
             };
+            let sqm_path = dir.join("mission.sqm");
+            // Missing attributes are reported at the trigger's `Attributes` class
+            let attributes_span = attributes.name().map_or(0..0, |name| name.span.clone());
             // Check for isServerOnly
-            if let Some((is_server_only, _)) = get_number(&attributes, "isServerOnly") {
+            if let Some((is_server_only, span)) = get_number(&attributes, "isServerOnly") {
                 if is_server_only == 0 {
-                    self.messages.push(Annotation::new(
-                        None,
-                        dir.join("mission.sqm").display().to_string(),
-                        0..0,
-                        "Trigger not set to server only".to_string(),
-                        Level::Error,
-                    ));
+                    self.messages.push(
+                        Annotation::new(
+                            Some(mission.0),
+                            sqm_path.display().to_string(),
+                            span.clone(),
+                            "Trigger not set to server only".to_string(),
+                            &rules::TRIGGER_SERVER_ONLY,
+                        )
+                        .with_fix(Fix::replace(
+                            Some(mission.0),
+                            &span,
+                            "1",
+                        )),
+                    );
                 }
             } else {
-                self.messages.push(Annotation::new(
-                    None,
-                    dir.join("mission.sqm").display().to_string(),
-                    0..0,
-                    "Trigger not set to server only".to_string(),
-                    Level::Error,
-                ));
+                self.messages.push(
+                    Annotation::new(
+                        Some(mission.0),
+                        sqm_path.display().to_string(),
+                        attributes_span.clone(),
+                        "Trigger not set to server only".to_string(),
+                        &rules::TRIGGER_SERVER_ONLY,
+                    )
+                    .with_fix(Fix::insert_entry(
+                        mission.0,
+                        &sqm_path,
+                        attributes.get_children(),
+                        "isServerOnly=1;",
+                    )),
+                );
             }
 
             // Check interval
-            if let Some((interval, _)) = get_float(&attributes, "triggerInterval") {
+            if let Some((interval, span)) = get_float(&attributes, "triggerInterval") {
                 if interval < 0.6 {
-                    self.messages.push(Annotation::new(
-                        None,
-                        dir.join("mission.sqm").display().to_string(),
-                        0..0,
-                        "Trigger interval is set too low (below 0.5 seconds)".to_string(),
-                        Level::Error,
-                    ));
+                    self.messages.push(
+                        Annotation::new(
+                            Some(mission.0),
+                            sqm_path.display().to_string(),
+                            span.clone(),
+                            "Trigger interval is set too low (below 0.5 seconds)".to_string(),
+                            &rules::TRIGGER_INTERVAL,
+                        )
+                        .with_fix(Fix::replace(
+                            Some(mission.0),
+                            &span,
+                            "1",
+                        )),
+                    );
                 }
             } else {
-                self.messages.push(Annotation::new(
-                    None,
-                    dir.join("mission.sqm").display().to_string(),
-                    0..0,
-                    "Trigger interval is set too low (below 0.5 seconds)".to_string(),
-                    Level::Error,
-                ));
+                self.messages.push(
+                    Annotation::new(
+                        Some(mission.0),
+                        sqm_path.display().to_string(),
+                        attributes_span.clone(),
+                        "Trigger interval is set too low (below 0.5 seconds)".to_string(),
+                        &rules::TRIGGER_INTERVAL,
+                    )
+                    .with_fix(Fix::insert_entry(
+                        mission.0,
+                        &sqm_path,
+                        attributes.get_children(),
+                        "triggerInterval=1;",
+                    )),
+                );
             }
         }
         if trigger.is_none() || waypoint.is_none() {
@@ -200,7 +241,7 @@ impl MissionCheck for TriggerChecks {
                 dir.join("mission.sqm").display().to_string(),
                 0..0,
                 "WaypointActivation link does not connect to a trigger and waypoint".to_string(),
-                Level::Error,
+                &rules::ACTIVATION_LINK,
             ));
             return;
         }
@@ -212,7 +253,7 @@ impl MissionCheck for TriggerChecks {
                 dir.join("mission.sqm").display().to_string(),
                 0..0,
                 "WaypointActivation link does not connect to a valid waypoint".to_string(),
-                Level::Error,
+                &rules::ACTIVATION_LINK,
             ));
             return;
         };
@@ -230,14 +271,14 @@ impl MissionCheck for TriggerChecks {
                     dir.join("mission.sqm").display().to_string(),
                     0..0,
                     "HOLD waypoint is linked to a trigger that isn't SKIP WAYPOINT".to_string(),
-                    Level::Error,
+                    &rules::HOLD_WAYPOINT,
                 ));
             }
             _ => {}
         }
     }
 
-    fn done(&self, _dir: &Path) -> Vec<Annotation> {
+    fn done(&self, _: (&Processed, &Config), _dir: &Path, _: &SpatialIndex) -> Vec<Annotation> {
         self.messages.clone()
     }
 }
@@ -3,10 +3,7 @@ use std::{collections::HashMap, path::Path, sync::OnceLock};
 use hemtt_config::Config;
 use hemtt_workspace::reporting::Processed;
 
-use crate::{
-    annotation::{Annotation, Level},
-    get_class, get_number,
-};
+use crate::{annotation::Annotation, fix::Fix, get_class, get_number, rules};
 
 pub fn time(
     dir: &Path,
@@ -49,7 +46,7 @@ pub fn time(
             ext_path.display().to_string(),
             0..0,
             "synixe_start_time is missing".to_string(),
-            Level::Error,
+            &rules::START_TIME,
         ));
         return messages;
     };
@@ -59,7 +56,7 @@ pub fn time(
             ext_path.display().to_string(),
             0..0,
             "synixe_start_time is not between 0 and 24".to_string(),
-            Level::Error,
+            &rules::START_TIME,
         ));
         return messages;
     }
@@ -69,7 +66,7 @@ pub fn time(
             sqm_path.display().to_string(),
             0..0,
             "Mission >> Intel is missing".to_string(),
-            Level::Error,
+            &rules::MISSION_TIME,
         ));
         return messages;
     };
@@ -83,7 +80,7 @@ pub fn time(
                     sqm_path.display().to_string(),
                     0..0,
                     "Mission filename does not contain a map name".to_string(),
-                    Level::Error,
+                    &rules::MISSION_TIME,
                 ));
                 return messages;
             };
@@ -93,20 +90,28 @@ pub fn time(
                     sqm_path.display().to_string(),
                     0..0,
                     "Mission >> Intel >> hour is missing".to_string(),
-                    Level::Error,
+                    &rules::MISSION_TIME,
                 ));
                 return messages;
             };
             (*mission_hour, 0..0)
         };
     if !(mission_hour + 1 == synixe_start_time || (mission_hour == 23 && synixe_start_time == 0)) {
-        messages.push(Annotation::new(
+        let fix = Fix::replace(
             Some(mission.0),
-            sqm_path.display().to_string(),
-            mission_hour_span,
-            format!("Editor hour needs to be 1 hour before synixe_start_time. Editor: {}, Description: {}", mission_hour, synixe_start_time),
-            Level::Error,
-        ));
+            &mission_hour_span,
+            ((synixe_start_time + 23) % 24).to_string(),
+        );
+        messages.push(
+            Annotation::new(
+                Some(mission.0),
+                sqm_path.display().to_string(),
+                mission_hour_span,
+                format!("Editor hour needs to be 1 hour before synixe_start_time. Editor: {}, Description: {}", mission_hour, synixe_start_time),
+                &rules::EDITOR_HOUR,
+            )
+            .with_fix(fix),
+        );
     }
 
     let (mission_minutes, mission_minutes_span) =
@@ -120,18 +125,18 @@ pub fn time(
                     sqm_path.display().to_string(),
                     0..0,
                     "Mission filename does not contain a map name".to_string(),
-                    Level::Error,
+                    &rules::MISSION_TIME,
                 ));
                 return messages;
             };
             let Some((mission_minutes, _)) = times.get(&map.to_lowercase()) else {
-                println!("Missing time for map {} in starts.txt", map);
+                eprintln!("Missing time for map {} in starts.txt", map);
                 messages.push(Annotation::new(
                     Some(mission.0),
                     sqm_path.display().to_string(),
                     0..0,
                     "Mission >> Intel >> minutes is missing".to_string(),
-                    Level::Error,
+                    &rules::MISSION_TIME,
                 ));
                 return messages;
             };
@@ -139,13 +144,17 @@ pub fn time(
         };
 
     if mission_minutes != 0 {
-        messages.push(Annotation::new(
-            Some(mission.0),
-            sqm_path.display().to_string(),
-            mission_minutes_span,
-            "Editor minutes needs to be 0".to_string(),
-            Level::Error,
-        ));
+        let fix = Fix::replace(Some(mission.0), &mission_minutes_span, "0");
+        messages.push(
+            Annotation::new(
+                Some(mission.0),
+                sqm_path.display().to_string(),
+                mission_minutes_span,
+                "Editor minutes needs to be 0".to_string(),
+                &rules::EDITOR_MINUTE,
+            )
+            .with_fix(fix),
+        );
     }
 
     messages
@@ -0,0 +1,218 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt::Write,
+    ops::Range,
+    path::Path,
+};
+
+use hemtt_config::Property;
+use hemtt_workspace::reporting::Processed;
+use serde::Serialize;
+
+use crate::report::Report;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+/// An edit that resolves a finding
+pub struct Fix {
+    /// Byte range in the original file that is replaced, empty for an insertion
+    pub range: Range<usize>,
+    /// The text that replaces the range
+    pub replacement: String,
+}
+
+impl Fix {
+    /// Replace a span, mapped back to the original file when it is from processed output
+    pub fn replace(
+        processed: Option<&Processed>,
+        span: &Range<usize>,
+        replacement: impl Into<String>,
+    ) -> Option<Self> {
+        if span.is_empty() {
+            return None;
+        }
+        Some(Self {
+            range: original(processed, span)?,
+            replacement: replacement.into(),
+        })
+    }
+
+    /// Insert an entry before the first property of a class, using the same indentation
+    pub fn insert_entry(
+        processed: &Processed,
+        path: &Path,
+        properties: &[Property],
+        entry: &str,
+    ) -> Option<Self> {
+        let name = match properties.first()? {
+            Property::Entry { name, .. } | Property::Delete(name) => name,
+            Property::MissingSemicolon(name, _) => name,
+            Property::Class(class) => class.name()?,
+        };
+        let offset = original(Some(processed), &name.span)?.start;
+        let content = std::fs::read_to_string(path).ok()?;
+        let line_start = content.get(..offset)?.rfind('\n').map_or(0, |i| i + 1);
+        let indent = &content[line_start..offset];
+        if !indent.chars().all(char::is_whitespace) {
+            return None;
+        }
+        Some(Self {
+            range: offset..offset,
+            replacement: format!("{}\n{}", entry, indent),
+        })
+    }
+}
+
+fn original(processed: Option<&Processed>, span: &Range<usize>) -> Option<Range<usize>> {
+    let Some(processed) = processed else {
+        return Some(span.clone());
+    };
+    let start = processed.mapping(span.start)?.original().start().offset();
+    let end = processed
+        .mapping(span.end.saturating_sub(1).max(span.start))?
+        .original()
+        .end()
+        .offset();
+    Some(start..end.max(start))
+}
+
+/// Apply the fixes of a report
+///
+/// With `dry_run` the files are left untouched and a unified diff of the changes is returned,
+/// otherwise the fixed findings are removed from the report and a summary is returned. Findings
+/// whose fix is skipped stay in the report, when it overlaps another fix or its file can not be
+/// read, like a file in a packed mission
+pub fn run(report: &mut Report, dry_run: bool) -> Result<String, String> {
+    let mut files: BTreeMap<&str, Vec<&Fix>> = BTreeMap::new();
+    for annotation in report.annotations() {
+        if let Some(fix) = &annotation.fix {
+            files.entry(&annotation.path).or_default().push(fix);
+        }
+    }
+    let mut out = String::new();
+    let mut applied: HashSet<(String, Fix)> = HashSet::new();
+    let mut count = 0;
+    for (path, fixes) in &files {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) => {
+                eprintln!("Skipping fixes in {}: {}", path, e);
+                continue;
+            }
+        };
+        let edits = edits(&content, fixes);
+        count += 1;
+        applied.extend(edits.iter().map(|fix| (path.to_string(), (*fix).clone())));
+        if dry_run {
+            out.push_str(&diff(path, &content, &edits));
+        } else {
+            std::fs::write(path, apply(&content, &edits))
+                .map_err(|e| format!("failed to write {}: {}", path, e))?;
+        }
+    }
+    if !dry_run {
+        let fixed = applied.len();
+        for mission in &mut report.missions {
+            mission.annotations.retain(|annotation| {
+                annotation
+                    .fix
+                    .as_ref()
+                    .is_none_or(|fix| !applied.contains(&(annotation.path.clone(), fix.clone())))
+            });
+        }
+        writeln!(out, "Applied {} fix(es) in {} file(s)", fixed, count).expect("write to string");
+    }
+    Ok(out)
+}
+
+/// Apply fixes to the content of a file, everything outside the fixed ranges is kept as is
+pub fn apply(content: &str, fixes: &[&Fix]) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut last = 0;
+    for fix in edits(content, fixes) {
+        out.push_str(&content[last..fix.range.start]);
+        out.push_str(&fix.replacement);
+        last = fix.range.end;
+    }
+    out.push_str(&content[last..]);
+    out
+}
+
+/// A unified diff of the lines changed by the fixes
+pub fn diff(path: &str, content: &str, fixes: &[&Fix]) -> String {
+    let edits = edits(content, fixes);
+    if edits.is_empty() {
+        return String::new();
+    }
+    // Group the edits into hunks of whole lines
+    let mut hunks: Vec<(Range<usize>, Vec<&Fix>)> = Vec::new();
+    for fix in edits {
+        let start = content[..fix.range.start].rfind('\n').map_or(0, |i| i + 1);
+        let end = content[fix.range.end..]
+            .find('\n')
+            .map_or(content.len(), |i| fix.range.end + i + 1);
+        match hunks.last_mut() {
+            Some((lines, fixes)) if start <= lines.end => {
+                lines.end = lines.end.max(end);
+                fixes.push(fix);
+            }
+            _ => hunks.push((start..end, vec![fix])),
+        }
+    }
+    let mut out = format!("--- a/{}\n+++ b/{}\n", path, path);
+    let mut delta: isize = 0;
+    for (lines, fixes) in hunks {
+        let old = &content[lines.clone()];
+        let shifted = fixes
+            .iter()
+            .map(|fix| Fix {
+                range: fix.range.start - lines.start..fix.range.end - lines.start,
+                replacement: fix.replacement.clone(),
+            })
+            .collect::<Vec<_>>();
+        let new = apply(old, &shifted.iter().collect::<Vec<_>>());
+        let old_line = content[..lines.start].matches('\n').count() + 1;
+        let old_count = old.lines().count();
+        let new_count = new.lines().count();
+        writeln!(
+            out,
+            "@@ -{},{} +{},{} @@",
+            old_line,
+            old_count,
+            old_line.saturating_add_signed(delta),
+            new_count
+        )
+        .expect("write to string");
+        for line in old.lines() {
+            writeln!(out, "-{}", line).expect("write to string");
+        }
+        for line in new.lines() {
+            writeln!(out, "+{}", line).expect("write to string");
+        }
+        delta += new_count as isize - old_count as isize;
+    }
+    out
+}
+
+/// Sort the fixes, dropping duplicates, overlapping fixes and ranges outside the content
+fn edits<'a>(content: &str, fixes: &[&'a Fix]) -> Vec<&'a Fix> {
+    let mut fixes = fixes
+        .iter()
+        .copied()
+        .filter(|fix| {
+            fix.range.end <= content.len()
+                && content.is_char_boundary(fix.range.start)
+                && content.is_char_boundary(fix.range.end)
+        })
+        .collect::<Vec<_>>();
+    fixes.sort_by_key(|fix| (fix.range.start, fix.range.end));
+    fixes.dedup();
+    let mut last = 0;
+    fixes.retain(|fix| {
+        if fix.range.start < last {
+            return false;
+        }
+        last = fix.range.end;
+        true
+    });
+    fixes
+}
@@ -1,7 +1,19 @@
 pub mod annotation;
+pub mod baseline;
+pub mod changes;
 pub mod checks;
+pub mod fix;
+pub mod lsp;
 pub mod mission;
+pub mod output;
+pub mod pbo;
+pub mod rapify;
+pub mod report;
+pub mod rules;
+pub mod settings;
+pub mod suppression;
 pub mod versions;
+pub mod watch;
 
 use std::ops::Range;
 
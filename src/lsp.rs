@@ -0,0 +1,453 @@
+use std::{
+    collections::HashMap,
+    io::{BufRead, Write},
+    path::{Path, PathBuf},
+};
+
+use serde_json::{json, Value};
+
+use crate::{
+    annotation::{Annotation, Level},
+    mission, rules,
+    settings::Settings,
+};
+
+/// Serve the Language Server Protocol over stdio
+///
+/// Missions are reviewed when a file in them is opened or saved, findings are published as
+/// diagnostics and hovering one shows the explanation of its code
+pub fn run(settings: &Settings) -> Result<(), String> {
+    let stdin = std::io::stdin();
+    let mut input = stdin.lock();
+    let mut server = Server {
+        settings,
+        root: std::env::current_dir().map_err(|e| format!("no working directory: {}", e))?,
+        diagnostics: HashMap::new(),
+        published: HashMap::new(),
+        sources: Sources::default(),
+        shutdown: false,
+    };
+    while let Some(message) = read_message(&mut input)? {
+        let method = message["method"].as_str().unwrap_or_default();
+        if method == "exit" {
+            return if server.shutdown {
+                Ok(())
+            } else {
+                Err("exit before shutdown".to_string())
+            };
+        }
+        // A failing request must not stop the server, the client keeps sending documents
+        if let Err(e) = server.handle(method, &message) {
+            log(&e);
+        }
+    }
+    Ok(())
+}
+
+struct Server<'a> {
+    settings: &'a Settings,
+    /// Working directory, annotation paths are relative to it
+    root: PathBuf,
+    /// Findings of each document, by URI
+    diagnostics: HashMap<String, Vec<Annotation>>,
+    /// Documents diagnostics were published for, by mission
+    published: HashMap<PathBuf, Vec<String>>,
+    /// Lines of the documents, for UTF-16 positions
+    sources: Sources,
+    shutdown: bool,
+}
+
+impl Server<'_> {
+    fn handle(&mut self, method: &str, message: &Value) -> Result<(), String> {
+        let id = message.get("id");
+        let params = &message["params"];
+        match method {
+            "initialize" => respond(
+                id,
+                json!({
+                    "capabilities": {
+                        "positionEncoding": "utf-16",
+                        "textDocumentSync": {
+                            "openClose": true,
+                            "change": 0,
+                            "save": { "includeText": false },
+                        },
+                        "hoverProvider": true,
+                    },
+                    "serverInfo": {
+                        "name": "missionreviewer",
+                        "version": env!("CARGO_PKG_VERSION"),
+                    },
+                }),
+            ),
+            "shutdown" => {
+                self.shutdown = true;
+                respond(id, Value::Null)
+            }
+            "textDocument/didOpen" | "textDocument/didSave" => {
+                let Some(path) = params["textDocument"]["uri"].as_str().and_then(uri_to_path)
+                else {
+                    return Ok(());
+                };
+                let Some(dir) = mission_for(&path) else {
+                    return Ok(());
+                };
+                if let Err(e) = self.review(&dir) {
+                    log(&format!("failed to review {}: {}", dir.display(), e));
+                }
+                Ok(())
+            }
+            "textDocument/hover" => {
+                // Clients escape characters like the `:` of a drive differently
+                let uri = params["textDocument"]["uri"]
+                    .as_str()
+                    .and_then(normalize_uri)
+                    .unwrap_or_default();
+                let line = params["position"]["line"].as_u64().unwrap_or_default() as usize;
+                let character =
+                    params["position"]["character"].as_u64().unwrap_or_default() as usize;
+                respond(
+                    id,
+                    self.diagnostics
+                        .get(&uri)
+                        .and_then(|annotations| {
+                            hover(annotations, &self.root, line, character, &mut self.sources)
+                        })
+                        .unwrap_or(Value::Null),
+                )
+            }
+            _ => match id {
+                Some(id) => send(&json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "error": {
+                        "code": -32601,
+                        "message": format!("unsupported method {}", method),
+                    },
+                })),
+                // Notifications that are not needed, like `initialized` and `didChange`
+                None => Ok(()),
+            },
+        }
+    }
+
+    /// Review a mission and publish its findings, clearing documents that no longer have any
+    fn review(&mut self, dir: &Path) -> Result<(), String> {
+        let relative = dir.strip_prefix(&self.root).unwrap_or(dir).to_path_buf();
+        let report = mission::check(&relative, self.settings)?;
+        let mut documents: HashMap<String, Vec<Annotation>> = HashMap::new();
+        for annotation in report.annotations {
+            let path = self.root.join(&annotation.path);
+            if path.is_file() {
+                documents
+                    .entry(path_to_uri(&path))
+                    .or_default()
+                    .push(annotation);
+            }
+        }
+        // The documents were saved, so their lines are read again
+        self.sources = Sources::default();
+        for uri in self.published.remove(&relative).unwrap_or_default() {
+            if !documents.contains_key(&uri) {
+                self.diagnostics.remove(&uri);
+                publish(&uri, &[], &self.root, &mut self.sources)?;
+            }
+        }
+        for (uri, annotations) in &documents {
+            publish(uri, annotations, &self.root, &mut self.sources)?;
+        }
+        self.published
+            .insert(relative, documents.keys().cloned().collect());
+        self.diagnostics.extend(documents);
+        Ok(())
+    }
+}
+
+/// Find the mission folder a file belongs to, the closest folder with a `mission.sqm`
+pub fn mission_for(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .skip(1)
+        .find(|dir| dir.join("mission.sqm").is_file())
+        .map(Path::to_path_buf)
+}
+
+#[derive(Debug, Default)]
+/// Lines of the files findings are in, LSP positions count UTF-16 code units instead of characters
+pub struct Sources(HashMap<PathBuf, Vec<String>>);
+
+impl Sources {
+    /// A zero based line of a file, `None` when the file can not be read
+    fn line(&mut self, path: &Path, line: usize) -> Option<&str> {
+        self.0
+            .entry(path.to_path_buf())
+            .or_insert_with(|| {
+                std::fs::read_to_string(path)
+                    .map(|content| content.lines().map(str::to_string).collect())
+                    .unwrap_or_default()
+            })
+            .get(line)
+            .map(String::as_str)
+    }
+
+    /// Convert a zero based character column to UTF-16 code units
+    fn utf16(&mut self, path: &Path, line: usize, column: usize) -> usize {
+        let Some(text) = self.line(path, line) else {
+            return column;
+        };
+        let mut chars = text.chars();
+        (0..column)
+            .map(|_| chars.next().map_or(1, char::len_utf16))
+            .sum()
+    }
+
+    /// Convert UTF-16 code units to a zero based character column
+    fn column(&mut self, path: &Path, line: usize, character: usize) -> usize {
+        let Some(text) = self.line(path, line) else {
+            return character;
+        };
+        let mut units = 0;
+        let mut column = 0;
+        for c in text.chars() {
+            units += c.len_utf16();
+            if units > character {
+                return column;
+            }
+            column += 1;
+        }
+        column + character - units
+    }
+}
+
+/// Convert an annotation to an LSP diagnostic, relative paths are resolved against `root`
+pub fn diagnostic(annotation: &Annotation, root: &Path, sources: &mut Sources) -> Value {
+    let mut diagnostic = json!({
+        "range": range(
+            sources,
+            &root.join(&annotation.path),
+            annotation.start_line,
+            annotation.start_column,
+            annotation.end_line,
+            annotation.end_column,
+        ),
+        "severity": match annotation.level {
+            Level::Error => 1,
+            Level::Warning => 2,
+            Level::Notice => 3,
+        },
+        "code": annotation.code,
+        "source": "missionreviewer",
+        "message": annotation.message,
+    });
+    if !annotation.related.is_empty() {
+        diagnostic["relatedInformation"] = annotation
+            .related
+            .iter()
+            .map(|related| {
+                json!({
+                    "location": {
+                        "uri": path_to_uri(&root.join(&related.path)),
+                        "range": range(
+                            sources,
+                            &root.join(&related.path),
+                            related.start_line,
+                            related.start_column,
+                            related.end_line,
+                            related.end_column,
+                        ),
+                    },
+                    "message": related.message,
+                })
+            })
+            .collect();
+    }
+    diagnostic
+}
+
+/// Hover for the findings at a zero based position, showing the explanation of their codes
+///
+/// `character` counts UTF-16 code units, like every LSP position
+pub fn hover(
+    annotations: &[Annotation],
+    root: &Path,
+    line: usize,
+    character: usize,
+    sources: &mut Sources,
+) -> Option<Value> {
+    let found = annotations
+        .iter()
+        .filter(|annotation| {
+            let column = sources.column(&root.join(&annotation.path), line, character);
+            contains(annotation, line, column)
+        })
+        .collect::<Vec<_>>();
+    let first = found.first()?;
+    let value = found
+        .iter()
+        .map(|annotation| match rules::code(&annotation.code) {
+            Some(code) => format!("**{}**\n\n{}", code.heading(), code.explanation),
+            None => format!("**{}**", annotation.title),
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n---\n\n");
+    Some(json!({
+        "contents": { "kind": "markdown", "value": value },
+        "range": range(
+            sources,
+            &root.join(&first.path),
+            first.start_line,
+            first.start_column,
+            first.end_line,
+            first.end_column,
+        ),
+    }))
+}
+
+fn contains(annotation: &Annotation, line: usize, character: usize) -> bool {
+    let start = annotation.start_line.saturating_sub(1);
+    let end = annotation.end_line.saturating_sub(1);
+    if line < start || line > end {
+        return false;
+    }
+    (line != start || character + 1 >= annotation.start_column)
+        && (line != end || character + 1 < annotation.end_column.max(annotation.start_column + 1))
+}
+
+/// A zero based range in UTF-16 code units from one based lines and character columns
+fn range(
+    sources: &mut Sources,
+    path: &Path,
+    start_line: usize,
+    start_column: usize,
+    end_line: usize,
+    end_column: usize,
+) -> Value {
+    let (start_line, end_line) = (start_line.saturating_sub(1), end_line.saturating_sub(1));
+    json!({
+        "start": {
+            "line": start_line,
+            "character": sources.utf16(path, start_line, start_column.saturating_sub(1)),
+        },
+        "end": {
+            "line": end_line,
+            "character": sources.utf16(path, end_line, end_column.saturating_sub(1)),
+        },
+    })
+}
+
+/// Convert a `file` URI to a path
+pub fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    let path = uri.strip_prefix("file://")?;
+    let mut bytes = Vec::with_capacity(path.len());
+    let mut chars = path.bytes();
+    while let Some(byte) = chars.next() {
+        if byte == b'%' {
+            let hex = [chars.next()?, chars.next()?];
+            bytes.push(u8::from_str_radix(std::str::from_utf8(&hex).ok()?, 16).ok()?);
+        } else {
+            bytes.push(byte);
+        }
+    }
+    let path = String::from_utf8(bytes).ok()?;
+    // `file:///C:/missions` on Windows
+    let path = match path.as_bytes() {
+        [b'/', drive, b':', ..] if drive.is_ascii_alphabetic() => path[1..].to_string(),
+        _ => path,
+    };
+    Some(PathBuf::from(path))
+}
+
+/// Escape a `file` URI the same way as [`path_to_uri`], so it can be used as a key
+pub fn normalize_uri(uri: &str) -> Option<String> {
+    uri_to_path(uri).map(|path| path_to_uri(&path))
+}
+
+/// Convert an absolute path to a `file` URI
+pub fn path_to_uri(path: &Path) -> String {
+    let path = path.display().to_string().replace('\\', "/");
+    let mut uri = String::from("file://");
+    if !path.starts_with('/') {
+        uri.push('/');
+    }
+    for byte in path.bytes() {
+        if byte.is_ascii_alphanumeric() || b"/-_.~:".contains(&byte) {
+            uri.push(byte as char);
+        } else {
+            uri.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    uri
+}
+
+fn publish(
+    uri: &str,
+    annotations: &[Annotation],
+    root: &Path,
+    sources: &mut Sources,
+) -> Result<(), String> {
+    send(&json!({
+        "jsonrpc": "2.0",
+        "method": "textDocument/publishDiagnostics",
+        "params": {
+            "uri": uri,
+            "diagnostics": annotations
+                .iter()
+                .map(|annotation| diagnostic(annotation, root, sources))
+                .collect::<Vec<_>>(),
+        },
+    }))
+}
+
+/// Log a message in the client's output, the server keeps running
+fn log(message: &str) {
+    eprintln!("{}", message);
+    let _ = send(&json!({
+        "jsonrpc": "2.0",
+        "method": "window/logMessage",
+        "params": { "type": 1, "message": message },
+    }));
+}
+
+fn respond(id: Option<&Value>, result: Value) -> Result<(), String> {
+    send(&json!({
+        "jsonrpc": "2.0",
+        "id": id.cloned().unwrap_or(Value::Null),
+        "result": result,
+    }))
+}
+
+fn send(message: &Value) -> Result<(), String> {
+    let body = message.to_string();
+    let mut stdout = std::io::stdout().lock();
+    write!(stdout, "Content-Length: {}\r\n\r\n{}", body.len(), body)
+        .and_then(|()| stdout.flush())
+        .map_err(|e| format!("failed to write to stdout: {}", e))
+}
+
+/// Read a message framed by a `Content-Length` header, `None` at the end of the input
+pub fn read_message(input: &mut impl BufRead) -> Result<Option<Value>, String> {
+    let mut length = None;
+    loop {
+        let mut header = String::new();
+        let read = input
+            .read_line(&mut header)
+            .map_err(|e| format!("failed to read from stdin: {}", e))?;
+        if read == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some(value) = header.strip_prefix("Content-Length:") {
+            length = value.trim().parse::<usize>().ok();
+        }
+    }
+    let length = length.ok_or("message without a Content-Length header")?;
+    let mut body = vec![0; length];
+    input
+        .read_exact(&mut body)
+        .map_err(|e| format!("failed to read from stdin: {}", e))?;
+    serde_json::from_slice(&body)
+        .map(Some)
+        .map_err(|e| format!("message is not JSON: {}", e))
+}
@@ -1,124 +1,352 @@
 use std::{
-    io::Write,
+    io::IsTerminal,
     path::{Path, PathBuf},
-    sync::RwLock,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        RwLock,
+    },
 };
 
-use missionreviewer::annotation::{Annotation, Level};
+use clap::{Parser, Subcommand, ValueEnum};
+use missionreviewer::{
+    annotation::{Annotation, Level},
+    baseline::Baseline,
+    changes, fix, output, pbo,
+    report::{Report, EXIT_CONFIG, EXIT_PARSE},
+    rules,
+    settings::Settings,
+};
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 
-const FLAT_FOLDERS: [&str; 2] = ["contracts", "specials"];
-const NESTED_FOLDERS: [&str; 1] = ["theatres"];
+#[derive(Parser)]
+#[command(version, about)]
+#[command(args_conflicts_with_subcommands = true)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// Only review missions whose path contains one of these, `.pbo` files and folders of
+    /// them are reviewed as packed missions
+    filters: Vec<String>,
+    #[arg(long, value_enum)]
+    /// Output format, defaults to `pretty` in a terminal and `log` otherwise
+    format: Option<Format>,
+    #[arg(long)]
+    /// File to write the output to, defaults to `missionreviewer.<format>`, or stdout for `pretty`
+    output: Option<PathBuf>,
+    #[arg(long, value_enum)]
+    /// Exit with 1 when a finding is at or above this level
+    ///
+    /// Without it, the exit code is 0 whenever the review ran. A mission that could not be parsed
+    /// always exits with 3
+    fail_on: Option<FailOn>,
+    #[arg(long, value_name = "REF")]
+    /// Only review missions with files changed since the merge base of this git ref and `HEAD`
+    ///
+    /// A change to a shared file, such as `starts.txt`, reviews all missions
+    changed_since: Option<String>,
+    #[arg(long, value_name = "FILE")]
+    /// Hide findings that are in this baseline file, so only new findings are reported
+    baseline: Option<PathBuf>,
+    #[arg(long, value_name = "FILE")]
+    /// Write all current findings to this baseline file and exit
+    write_baseline: Option<PathBuf>,
+    #[arg(long)]
+    /// Rewrite the files to resolve findings that have a single obvious fix
+    fix: bool,
+    #[arg(long, requires = "fix")]
+    /// With `--fix`, print a diff of the fixes instead of writing them
+    dry_run: bool,
+}
 
-fn main() {
-    let messages = RwLock::new(Vec::new());
+#[derive(Subcommand)]
+enum Command {
+    /// List every rule with its codes, default level and explanation
+    Rules,
+    /// Review a mission every time it is saved
+    Watch {
+        /// The mission folder
+        dir: PathBuf,
+    },
+    /// Serve the Language Server Protocol over stdio
+    Lsp,
+    /// Review a mission and pack it into `<folder name>.pbo` when it has no errors
+    Pack {
+        /// The mission folder
+        dir: PathBuf,
+        #[arg(long)]
+        /// File to write the archive to, defaults to `<folder name>.pbo` in the working directory
+        output: Option<PathBuf>,
+    },
+}
 
-    let prefixes = std::env::args().skip(1).collect::<Vec<String>>();
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    /// `||` delimited lines, read by the GitHub action
+    Log,
+    /// SARIF 2.1.0, for code scanning and IDE viewers
+    Sarif,
+    /// JSON report, following `schema/report.schema.json`
+    Json,
+    /// JUnit XML, a testsuite per mission and a testcase per rule
+    Junit,
+    /// Findings grouped by mission with source snippets, for a terminal
+    Pretty,
+    /// Summary table with collapsible findings, for `$GITHUB_STEP_SUMMARY` or a PR comment
+    Markdown,
+}
 
-    let mut missions = Vec::new();
+#[derive(Clone, Copy, ValueEnum)]
+enum FailOn {
+    Notice,
+    Warning,
+    Error,
+}
 
-    for folder in FLAT_FOLDERS {
-        if !PathBuf::from(folder).exists() {
-            continue;
+impl FailOn {
+    const fn level(self) -> Level {
+        match self {
+            Self::Notice => Level::Notice,
+            Self::Warning => Level::Warning,
+            Self::Error => Level::Error,
         }
-        for mission in std::fs::read_dir(folder).unwrap() {
-            let mission = mission.unwrap();
-            if !mission.path().is_dir() {
-                continue;
-            }
-            if prefixes.is_empty()
-                || prefixes
-                    .iter()
-                    .any(|prefix| mission.path().display().to_string().contains(prefix))
-            {
-                missions.push(mission.path());
-            }
+    }
+}
+
+impl Format {
+    const fn default_output(self) -> Option<&'static str> {
+        Some(match self {
+            Self::Log => "missionreviewer.log",
+            Self::Sarif => "missionreviewer.sarif",
+            Self::Json => "missionreviewer.json",
+            Self::Junit => "missionreviewer.xml",
+            Self::Markdown => "missionreviewer.md",
+            Self::Pretty => return None,
+        })
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    if let Some(Command::Rules) = cli.command {
+        print_rules();
+        return;
+    }
+
+    let settings = match Settings::load(Path::new(".")) {
+        Ok(settings) => settings,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(EXIT_CONFIG);
+        }
+    };
+
+    if let Some(Command::Lsp) = cli.command {
+        if let Err(e) = missionreviewer::lsp::run(&settings) {
+            eprintln!("{}", e);
+            std::process::exit(1);
         }
+        return;
     }
-    for folder in NESTED_FOLDERS {
-        if !PathBuf::from(folder).exists() {
-            continue;
+
+    if let Some(Command::Pack { dir, output }) = cli.command {
+        pack(&dir, output, &settings);
+        return;
+    }
+
+    if let Some(Command::Watch { dir }) = cli.command {
+        if !dir.is_dir() {
+            eprintln!("{} is not a folder", dir.display());
+            std::process::exit(EXIT_CONFIG);
         }
-        for subfolder in std::fs::read_dir(folder).unwrap() {
-            let subfolder = subfolder.unwrap();
-            if !subfolder.path().is_dir() {
-                continue;
+        missionreviewer::watch::run(
+            &dir,
+            &settings,
+            std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none(),
+        );
+    }
+
+    let reports = RwLock::new(Vec::new());
+
+    let baseline = cli
+        .baseline
+        .as_deref()
+        .map(|path| match Baseline::load(path) {
+            Ok(baseline) => baseline,
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(EXIT_CONFIG);
             }
-            for mission in std::fs::read_dir(subfolder.path()).unwrap() {
-                let mission = mission.unwrap();
-                if !mission.path().is_dir() {
-                    continue;
-                }
-                if prefixes.is_empty()
-                    || prefixes
-                        .iter()
-                        .any(|prefix| mission.path().display().to_string().contains(prefix))
-                {
-                    missions.push(mission.path());
-                }
+        });
+
+    let (archives, filters) = pbo::archives(&cli.filters);
+    let mut missions = if archives.is_empty() || !filters.is_empty() {
+        settings.missions(&filters)
+    } else {
+        Vec::new()
+    };
+    if let Some(reference) = &cli.changed_since {
+        match changes::changed_files(reference) {
+            Ok(changed) => missions = changes::select(&settings, missions, &changed),
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(EXIT_CONFIG);
             }
         }
     }
+    missions.extend(archives);
+    let failed = AtomicBool::new(false);
 
     missions.par_iter().for_each(|mission| {
-        if !mission.is_dir() {
+        let result = if pbo::is_archive(mission) {
+            pbo::check(mission, &settings)
+        } else if mission.is_dir() {
+            missionreviewer::mission::check(mission, &settings)
+        } else {
             return;
+        };
+        match result {
+            Err(e) => {
+                eprintln!("{}", e);
+                failed.store(true, Ordering::Relaxed);
+            }
+            Ok(report) => {
+                reports.write().unwrap().push(report);
+            }
         }
-        if let Err(e) = check_prefix(mission, false) {
+    });
+
+    let mut report = Report::new(reports.into_inner().unwrap());
+    if let Some(path) = cli.write_baseline {
+        let baseline = Baseline::new(&report);
+        if let Err(e) = baseline.save(&path) {
             eprintln!("{}", e);
+            std::process::exit(EXIT_CONFIG);
         }
-        match missionreviewer::mission::check(mission) {
+        println!(
+            "Wrote {} findings to {}",
+            baseline.findings.len(),
+            path.display()
+        );
+        return;
+    }
+    if let Some(baseline) = baseline {
+        let hidden = baseline.apply(&mut report);
+        eprintln!("Hid {} findings that are in the baseline", hidden);
+    }
+    if cli.fix {
+        match fix::run(&mut report, cli.dry_run) {
+            Ok(out) if cli.dry_run => {
+                print!("{}", out);
+                return;
+            }
+            Ok(out) => eprint!("{}", out),
             Err(e) => {
                 eprintln!("{}", e);
+                std::process::exit(EXIT_CONFIG);
             }
-            Ok(m) => {
-                messages.write().unwrap().extend(m);
-            }
+        }
+    }
+    let messages = report.annotations().cloned().collect::<Vec<_>>();
+    let format = cli.format.unwrap_or_else(|| {
+        if std::io::stdout().is_terminal() {
+            Format::Pretty
+        } else {
+            Format::Log
         }
     });
+    let path = cli
+        .output
+        .or_else(|| format.default_output().map(PathBuf::from));
+    let content = match format {
+        Format::Log => messages.iter().map(Annotation::line).collect::<String>(),
+        Format::Sarif => output::sarif::render(&messages),
+        Format::Json => output::json::render(&report),
+        Format::Junit => output::junit::render(&report),
+        Format::Markdown => output::markdown::render(&report),
+        Format::Pretty => output::pretty::render(
+            &report,
+            path.is_none()
+                && std::io::stdout().is_terminal()
+                && std::env::var_os("NO_COLOR").is_none(),
+        ),
+    };
+    if let Some(path) = path {
+        std::fs::write(&path, content).unwrap();
+        println!("Wrote {} messages to {}", messages.len(), path.display());
+    } else {
+        print!("{}", content);
+    }
 
-    let messages = messages.read().unwrap();
-    let mut out = std::fs::File::create("missionreviewer.log").unwrap();
-    for message in messages.iter() {
-        out.write_all(message.line().as_bytes()).unwrap();
+    // A mission that could not be parsed fails the run even without `--fail-on`
+    if failed.into_inner() || report.missions.iter().any(|mission| !mission.parsed()) {
+        std::process::exit(EXIT_PARSE);
+    }
+    if let Some(fail_on) = cli.fail_on {
+        std::process::exit(report.exit_code(fail_on.level()));
     }
-    println!("Wrote {} messages to missionreviewer.log", messages.len());
 }
 
-fn check_prefix(dir: &Path, nested: bool) -> Result<Vec<Annotation>, String> {
-    let mut messages = vec![];
+fn pack(dir: &PathBuf, output: Option<PathBuf>, settings: &Settings) {
     if !dir.is_dir() {
-        messages.push(Annotation::new(
-            None,
-            dir.to_path_buf().display().to_string(),
-            0..0,
-            "Not a directory".to_string(),
-            Level::Error,
-        ));
-        return Ok(messages);
-    }
-    let allowed = match nested {
-        true => {
-            vec!["CCO", "TCO", "TT"]
-        }
-        false => {
-            vec!["CO", "SCO", "TRA"]
+        eprintln!("{} is not a folder", dir.display());
+        std::process::exit(EXIT_CONFIG);
+    }
+    let report = match missionreviewer::mission::check(dir, settings) {
+        Ok(report) => Report::new(vec![report]),
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(EXIT_PARSE);
         }
     };
-    if !allowed.iter().any(|prefix| {
-        dir.file_name()
-            .unwrap()
-            .to_str()
-            .unwrap()
-            .starts_with(*prefix)
-    }) {
-        messages.push(Annotation::new(
-            None,
-            dir.to_path_buf().display().to_string(),
-            0..0,
-            format!("Invalid prefix for {}", dir.display()),
-            Level::Error,
-        ));
-    }
-    Ok(messages)
+    print!(
+        "{}",
+        output::pretty::render(
+            &report,
+            std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none(),
+        )
+    );
+    let code = report.exit_code(Level::Error);
+    if code != 0 {
+        eprintln!("Not packing {}, fix the errors first", dir.display());
+        std::process::exit(code);
+    }
+    let output = output.unwrap_or_else(|| {
+        PathBuf::from(format!(
+            "{}.pbo",
+            dir.file_name().unwrap_or_default().to_string_lossy()
+        ))
+    });
+    match pbo::pack(dir, &output) {
+        Ok(count) => println!("Packed {} files into {}", count, output.display()),
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(EXIT_CONFIG);
+        }
+    }
+}
+
+fn print_rules() {
+    for rule in &rules::RULES {
+        println!(
+            "{} ({})",
+            rule.id,
+            rule.types
+                .iter()
+                .map(|synixe_type| rules::type_name(*synixe_type))
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        for code in rule.codes {
+            println!(
+                "  {} {:<24} {:<8} {}",
+                code.code,
+                code.slug,
+                code.level.to_string(),
+                code.title
+            );
+            println!("        {}", code.explanation);
+        }
+        println!();
+    }
 }
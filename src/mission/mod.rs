@@ -0,0 +1,538 @@
+pub mod model;
+pub mod spatial;
+
+use std::{
+    hash::{DefaultHasher, Hash, Hasher},
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use hemtt_common::config::{PDriveOption, PreprocessorOptions};
+use hemtt_config::{Class, Config, ConfigReport, Number, Property, Value};
+use hemtt_preprocessor::Processor;
+use hemtt_workspace::{
+    reporting::{Processed, WorkspaceFiles},
+    LayerType, Workspace,
+};
+
+use crate::{
+    annotation::Annotation,
+    checks::{
+        objects::{
+            cover_map::CoverMapCheck, cup_parking::CUPParking, hostiles::HostilesCheck,
+            players::PlayerCheck, shops::ShopCheck, spawners::SpawnersCheck,
+            spectator::RequireSpectator, trigger::TriggerChecks, zeus::ZeusModule,
+        },
+        run_checks, MissionCheck,
+    },
+    get_class, get_number, get_string, rapify,
+    report::MissionReport,
+    rules,
+    settings::{Settings, Severities},
+    suppression, versions, GetChildren,
+};
+
+pub fn check(dir: &PathBuf, settings: &Settings) -> Result<MissionReport, String> {
+    check_at(dir, dir, settings)
+}
+
+/// Review the mission in `dir`, using the roots and overrides of the mission at `location`
+///
+/// The location differs from the folder for packed missions, which are reviewed unpacked
+pub fn check_at(
+    dir: &PathBuf,
+    location: &Path,
+    settings: &Settings,
+) -> Result<MissionReport, String> {
+    let mut report = MissionReport::new(dir);
+    let mut messages = vec![];
+    eprintln!("Checking {}", location.display());
+    let root = settings.root_for(location);
+    if let Some(root) = root {
+        messages.extend(root.check_prefix(location));
+    }
+    let (mission_processed, mission) = match read_mission(dir) {
+        Ok(config) => config,
+        Err(errors) => {
+            messages.extend(errors);
+            report.annotations = finish(dir, &settings.severities(location, None), messages);
+            return Ok(report);
+        }
+    };
+    let binarized = is_binarized(dir);
+    if binarized {
+        messages.push(Annotation::new(
+            None,
+            dir.join("mission.sqm").display().to_string(),
+            0..0,
+            "`mission.sqm` is binarized, save it with binarizationWanted=0".to_string(),
+            &rules::MISSION_BINARIZED,
+        ));
+    }
+    let (version, config_processed, config) = match read_description(dir) {
+        Ok(config) => config,
+        Err(errors) => {
+            messages.extend(errors);
+            report.annotations = finish(dir, &settings.severities(location, None), messages);
+            return Ok(report);
+        }
+    };
+    let (synixe_type, synixe_type_span) =
+        get_number(config.config(), "synixe_type").unwrap_or_default();
+    let severities = settings.severities(location, Some(synixe_type));
+    let thresholds = settings.thresholds(location, Some(synixe_type));
+    report.name = get_string(config.config(), "OnLoadName").map(|(name, _)| name.to_string());
+    report.template_version = Some(version);
+    report.synixe_type = Some(synixe_type);
+    report.player_count = Some(player_count(mission.config()));
+    match version {
+        2 => {
+            messages.append(&mut versions::v2::check(
+                dir,
+                (&mission_processed, mission.config()),
+                (&config_processed, config.config()),
+                &mut report.checks,
+            )?);
+        }
+        3 => {
+            messages.append(&mut versions::v3::check(
+                dir,
+                (&mission_processed, mission.config()),
+                (&config_processed, config.config()),
+                &mut report.checks,
+            )?);
+        }
+        _ => {
+            messages.push(Annotation::new(
+                Some(&config_processed),
+                dir.join("do_not_edit")
+                    .join("description.ext")
+                    .display()
+                    .to_string(),
+                0..0,
+                format!("Unknown synixe_template {}", version),
+                &rules::UNKNOWN_TEMPLATE,
+            ));
+        }
+    }
+    if let Some(root) = root.filter(|root| !root.allows_type(synixe_type)) {
+        messages.push(Annotation::new(
+            Some(&config_processed),
+            dir.join("edit_me")
+                .join("description.ext")
+                .display()
+                .to_string(),
+            synixe_type_span.clone(),
+            format!(
+                "synixe_type {} is not allowed in {}",
+                synixe_type,
+                root.path.display()
+            ),
+            &rules::TYPE_NOT_ALLOWED,
+        ));
+    }
+    let resistance_west = get_class(mission.config(), "Mission.Intel")
+        .and_then(|intel| get_number(intel, "resistanceWest").map(|(v, _)| v))
+        .unwrap_or(0);
+    let mut global_checks: Vec<Box<dyn MissionCheck>> = vec![
+        Box::new(TriggerChecks::new()),
+        Box::new(CUPParking::new()),
+        Box::new(ZeusModule::new()),
+        Box::new(CoverMapCheck::new(
+            matches!(synixe_type, 0 | 1),
+            resistance_west,
+        )),
+        Box::new(HostilesCheck::new(
+            resistance_west,
+            thresholds.hostile_distance,
+        )),
+    ];
+    // 0: Contract, 1: Sub-Contract, 2: Training, 3: Special
+    global_checks.append(&mut match synixe_type {
+        0 | 1 => {
+            vec![
+                Box::new(PlayerCheck::new(dir, true)),
+                Box::new(SpawnersCheck::new(
+                    true,
+                    version,
+                    get_number(config.config(), "synixe_no_vehicles")
+                        .map(|(v, _)| v == 1)
+                        .unwrap_or_default(),
+                )),
+                Box::new(ShopCheck::new(
+                    thresholds.required_shops,
+                    thresholds.shop_distance,
+                )),
+                Box::new(RequireSpectator::new()),
+            ]
+        }
+        2 => vec![
+            Box::new(PlayerCheck::new(dir, true)),
+            Box::new(SpawnersCheck::new(false, version, false)),
+            Box::new(ShopCheck::new(
+                thresholds.required_shops,
+                thresholds.shop_distance,
+            )),
+        ],
+        3 => vec![
+            Box::new(PlayerCheck::new(dir, false)),
+            Box::new(SpawnersCheck::new(false, version, false)),
+            Box::new(TriggerChecks::new()),
+        ],
+        _ => {
+            messages.push(Annotation::new(
+                Some(&config_processed),
+                dir.join("edit_me")
+                    .join("description.ext")
+                    .display()
+                    .to_string(),
+                synixe_type_span,
+                format!("Unknown synixe_type {}", synixe_type),
+                &rules::UNKNOWN_TYPE,
+            ));
+            vec![]
+        }
+    });
+    global_checks.retain(|check| severities.enabled(check.rule()));
+    report.checks.retain(|rule| severities.enabled(rule));
+    for check in &global_checks {
+        if !report.checks.iter().any(|rule| rule == check.rule()) {
+            report.checks.push(check.rule().to_string());
+        }
+    }
+    messages.extend(run_checks(
+        dir,
+        global_checks,
+        (&mission_processed, mission.config()),
+    ));
+    if binarized {
+        // Spans point into the decoded copy, which is not the file in the mission
+        let sqm_path = dir.join("mission.sqm").display().to_string();
+        for annotation in messages.iter_mut().filter(|a| a.path == sqm_path) {
+            annotation.start_line = 1;
+            annotation.end_line = 1;
+            annotation.start_column = 1;
+            annotation.end_column = 1;
+            annotation.fix = None;
+            annotation.related.clear();
+        }
+    }
+    report.annotations = finish(dir, &severities, messages);
+    Ok(report)
+}
+
+/// Apply the suppressions and the severities of a mission to its annotations
+///
+/// Severities are applied last, so they also cover the notices about the suppressions
+fn finish(dir: &Path, severities: &Severities, messages: Vec<Annotation>) -> Vec<Annotation> {
+    severities.apply(suppression::apply(&suppression::read(dir), messages))
+}
+
+/// Count the playable units in a mission
+pub fn player_count(mission: &Config) -> usize {
+    fn count(entities: &Class) -> usize {
+        entities
+            .get_children()
+            .iter()
+            .map(|child| {
+                let Property::Class(class) = child else {
+                    return 0;
+                };
+                match get_string(class, "dataType") {
+                    Some(("Group" | "Layer", _)) => get_class(class, "Entities").map_or(0, count),
+                    Some(("Object", _)) => {
+                        let playable = get_class(class, "Attributes").is_some_and(|attributes| {
+                            get_number(attributes, "isPlayer").is_some_and(|(v, _)| v == 1)
+                                || get_number(attributes, "isPlayable").is_some_and(|(v, _)| v == 1)
+                        });
+                        usize::from(playable)
+                    }
+                    _ => 0,
+                }
+            })
+            .sum()
+    }
+    get_class(mission, "Mission.Entities").map_or(0, count)
+}
+
+/// Check if a mission's `mission.sqm` is binarized
+pub fn is_binarized(dir: &Path) -> bool {
+    let mut signature = [0; 4];
+    std::fs::File::open(dir.join("mission.sqm"))
+        .and_then(|mut file| file.read_exact(&mut signature))
+        .is_ok_and(|()| rapify::is_rapified(&signature))
+}
+
+/// A temporary folder, removed with everything in it when dropped
+struct TempDir(PathBuf);
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Write a decoded `mission.sqm` to its own temporary folder, returning the folder
+fn write_derapified(dir: &Path, text: &str) -> Result<TempDir, String> {
+    let mut hasher = DefaultHasher::new();
+    dir.hash(&mut hasher);
+    let source = TempDir(std::env::temp_dir().join(format!(
+        "missionreviewer-{}-{:x}",
+        std::process::id(),
+        hasher.finish()
+    )));
+    std::fs::create_dir_all(&source.0)
+        .and_then(|()| std::fs::write(source.0.join("mission.sqm"), text))
+        .map_err(|e| format!("failed to write {}: {}", source.0.display(), e))?;
+    Ok(source)
+}
+
+pub fn read_description(dir: &PathBuf) -> Result<(u8, Processed, ConfigReport), Vec<Annotation>> {
+    let description = dir.join("description.ext");
+    if !description.is_file() {
+        return Err(vec![Annotation::new(
+            None,
+            description.display().to_string(),
+            0..1,
+            "`description.ext` is missing".to_string(),
+            &rules::DESCRIPTION_MISSING,
+        )]);
+    }
+    if let Err(e) = std::fs::read_to_string(&description) {
+        return Err(vec![Annotation::new(
+            None,
+            description.display().to_string(),
+            0..1,
+            format!("`description.ext` is invalid: {}", e),
+            &rules::DESCRIPTION_INVALID,
+        )]);
+    };
+    let workspace = Workspace::builder()
+        .physical(dir, LayerType::Source)
+        .finish(None, false, &PDriveOption::Disallow)
+        .expect("Failed to create workspace");
+    let processed = match Processor::run(
+        &workspace
+            .join("description.ext")
+            .expect("Failed to join path"),
+        &PreprocessorOptions::default(),
+    ) {
+        Ok(processed) => processed,
+        Err((_, hemtt_preprocessor::Error::Code(e))) => {
+            return Err(vec![Annotation::new(
+                None,
+                description.display().to_string(),
+                0..1,
+                format!(
+                    "`description.ext` failed to process: {}",
+                    e.diagnostic()
+                        .expect("diagnostic")
+                        .to_string(&WorkspaceFiles::new())
+                ),
+                &rules::DESCRIPTION_PROCESS,
+            )]);
+        }
+        Err((_, e)) => {
+            return Err(vec![Annotation::new(
+                None,
+                description.display().to_string(),
+                0..1,
+                format!("`description.ext` failed to process: {}", e),
+                &rules::DESCRIPTION_PROCESS,
+            )]);
+        }
+    };
+    match hemtt_config::parse(None, &processed) {
+        Ok(config) => {
+            let version = match config.config().0.iter().find(|c| {
+                if let Property::Entry {
+                    name,
+                    value: Value::Number(_),
+                    ..
+                } = c
+                {
+                    name.as_str() == "synixe_template"
+                } else {
+                    false
+                }
+            }) {
+                Some(value) => {
+                    let Property::Entry {
+                        value: Value::Number(Number::Int32 { value, .. }),
+                        ..
+                    } = value
+                    else {
+                        panic!("Expected entry");
+                    };
+                    *value as u8
+                }
+                None => 2,
+            };
+            let processed = match Processor::run(
+                &workspace
+                    .join("edit_me")
+                    .expect("Failed to join path")
+                    .join("description.ext")
+                    .expect("Failed to join path"),
+                &PreprocessorOptions::default(),
+            ) {
+                Ok(processed) => processed,
+                Err((_, hemtt_preprocessor::Error::Code(e))) => {
+                    return Err(vec![Annotation::new(
+                        None,
+                        description.display().to_string(),
+                        0..1,
+                        format!(
+                            "`description.ext` failed to process: {}",
+                            e.diagnostic()
+                                .expect("diagnostic")
+                                .to_string(&WorkspaceFiles::new())
+                        ),
+                        &rules::DESCRIPTION_PROCESS,
+                    )]);
+                }
+                Err((_, e)) => {
+                    return Err(vec![Annotation::new(
+                        None,
+                        description.display().to_string(),
+                        0..1,
+                        format!("`description.ext` failed to process: {}", e),
+                        &rules::DESCRIPTION_PROCESS,
+                    )]);
+                }
+            };
+            match hemtt_config::parse(None, &processed) {
+                Ok(config) => Ok((version, processed, config)),
+                Err(e) => Err(e
+                    .iter()
+                    .map(|e| {
+                        Annotation::new(
+                            None,
+                            description.display().to_string(),
+                            0..1,
+                            format!(
+                                "`description.ext` failed to process: {}",
+                                e.diagnostic()
+                                    .expect("diagnositc")
+                                    .to_string(&WorkspaceFiles::new())
+                            ),
+                            &rules::DESCRIPTION_PROCESS,
+                        )
+                    })
+                    .collect()),
+            }
+        }
+        Err(e) => Err(e
+            .iter()
+            .map(|e| {
+                Annotation::new(
+                    None,
+                    description.display().to_string(),
+                    0..1,
+                    format!(
+                        "`description.ext` failed to process: {}",
+                        e.diagnostic()
+                            .expect("diagnositc")
+                            .to_string(&WorkspaceFiles::new())
+                    ),
+                    &rules::DESCRIPTION_PROCESS,
+                )
+            })
+            .collect()),
+    }
+}
+
+pub fn read_mission(dir: &PathBuf) -> Result<(Processed, ConfigReport), Vec<Annotation>> {
+    let description = dir.join("mission.sqm");
+    if !description.is_file() {
+        return Err(vec![Annotation::new(
+            None,
+            description.display().to_string(),
+            0..1,
+            "`mission.sqm` is missing".to_string(),
+            &rules::MISSION_MISSING,
+        )]);
+    }
+    let bytes = std::fs::read(&description).unwrap_or_default();
+    // A binarized mission is decoded to a copy, so it is processed like a text mission
+    // The decoded copy is removed when `temp` is dropped, also when processing fails
+    let temp = if rapify::is_rapified(&bytes) {
+        match rapify::derapify(&bytes).and_then(|text| write_derapified(dir, &text)) {
+            Ok(temp) => Some(temp),
+            Err(e) => {
+                return Err(vec![Annotation::new(
+                    None,
+                    description.display().to_string(),
+                    0..1,
+                    format!("`mission.sqm` is binarized and could not be decoded: {}", e),
+                    &rules::MISSION_INVALID,
+                )]);
+            }
+        }
+    } else if String::from_utf8(bytes).is_err() {
+        return Err(vec![Annotation::new(
+            None,
+            description.display().to_string(),
+            0..1,
+            "`mission.sqm` is binarized or invalid".to_string(),
+            &rules::MISSION_INVALID,
+        )]);
+    } else {
+        None
+    };
+    let source = temp.as_ref().map_or(dir, |temp| &temp.0);
+    let workspace = Workspace::builder()
+        .physical(source, LayerType::Source)
+        .finish(None, false, &PDriveOption::Disallow)
+        .expect("Failed to create workspace");
+    let processed = match Processor::run(
+        &workspace.join("mission.sqm").expect("Failed to join path"),
+        &PreprocessorOptions::default(),
+    ) {
+        Ok(processed) => processed,
+        Err((_, hemtt_preprocessor::Error::Code(e))) => {
+            return Err(vec![Annotation::new(
+                None,
+                description.display().to_string(),
+                0..1,
+                format!(
+                    "`mission.sqm` failed to process: {}",
+                    e.diagnostic()
+                        .expect("diagnostic")
+                        .to_string(&WorkspaceFiles::new())
+                ),
+                &rules::MISSION_PROCESS,
+            )]);
+        }
+        Err((_, e)) => {
+            return Err(vec![Annotation::new(
+                None,
+                description.display().to_string(),
+                0..1,
+                format!("`mission.sqm` failed to process: {}", e),
+                &rules::MISSION_PROCESS,
+            )]);
+        }
+    };
+    drop(temp);
+    match hemtt_config::parse(None, &processed) {
+        Ok(config) => Ok((processed, config)),
+        Err(e) => Err(e
+            .iter()
+            .map(|e| {
+                Annotation::new(
+                    None,
+                    description.display().to_string(),
+                    0..1,
+                    format!(
+                        "`mission.sqm` failed to process: {}",
+                        e.diagnostic()
+                            .expect("diagnositc")
+                            .to_string(&WorkspaceFiles::new())
+                    ),
+                    &rules::MISSION_PROCESS,
+                )
+            })
+            .collect()),
+    }
+}
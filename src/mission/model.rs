@@ -0,0 +1,498 @@
+use std::{collections::HashMap, ops::Range};
+
+use hemtt_config::{Class, Config, Item, Number, Property, Value};
+
+use crate::{get_array, get_class, get_number, get_string, GetChildren};
+
+#[derive(Debug, Clone, PartialEq)]
+/// A value with its span in the processed `mission.sqm`
+pub struct Spanned<T> {
+    pub value: T,
+    pub span: Range<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// The value of an attribute
+pub enum AttributeValue {
+    Int(i64),
+    Float(f32),
+    Str(String),
+    Array(Vec<AttributeValue>),
+}
+
+impl AttributeValue {
+    /// The value as an integer, floats are truncated
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            Self::Int(value) => Some(*value),
+            Self::Float(value) => Some(*value as i64),
+            _ => None,
+        }
+    }
+
+    /// The value as a float
+    pub fn as_float(&self) -> Option<f32> {
+        match self {
+            Self::Int(value) => Some(*value as f32),
+            Self::Float(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Self::Str(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+/// The entities and connections of a `mission.sqm`
+pub struct Mission {
+    /// Every entity, in the order of the file, parents before their children
+    pub entities: Vec<Entity>,
+    /// Every connection between entities
+    pub connections: Vec<Connection>,
+    /// Index into `entities` by entity ID
+    ids: HashMap<i32, usize>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// An entity in `Mission >> Entities`, or nested in a group or layer
+pub struct Entity {
+    pub id: Option<i32>,
+    /// `dataType`, like `Object` or `Marker`
+    pub data_type: String,
+    /// `type`, the class name of an object, logic or trigger, or the type of a waypoint
+    pub class_type: Option<Spanned<String>>,
+    /// `side` of an object or group
+    pub side: Option<Spanned<String>>,
+    /// Position as stored, `[x, altitude, y]`
+    pub position: Option<[f32; 3]>,
+    /// Rotation around each axis in radians, as stored
+    pub rotation: Option<[f32; 3]>,
+    /// Entries of the `Attributes` class
+    pub attributes: HashMap<String, Spanned<AttributeValue>>,
+    /// Values of the `CustomAttributes`, by property
+    pub custom_attributes: HashMap<String, Spanned<AttributeValue>>,
+    /// ID of the group the entity is in
+    pub group: Option<i32>,
+    /// Names of the layers the entity is in, outermost first
+    pub layers: Vec<String>,
+    /// Span of the entity's class name
+    pub span: Range<usize>,
+    pub kind: Kind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// Data that depends on the `dataType` of an entity
+pub enum Kind {
+    Object(Object),
+    Group(Group),
+    Layer(Layer),
+    Waypoint(Waypoint),
+    Marker(Marker),
+    Trigger(Trigger),
+    Logic(Logic),
+    Other,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Object {
+    /// The unit is the player in singleplayer
+    pub player: bool,
+    /// The unit is playable
+    pub playable: bool,
+    /// `description` of a playable unit, shown in the lobby
+    pub description: Option<Spanned<String>>,
+    /// The object has crew, set for vehicles with units in them
+    pub crew: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Group {
+    /// IDs of the units in the group, the first one is its leader
+    pub units: Vec<i32>,
+    /// IDs of the waypoints of the group, in order
+    pub waypoints: Vec<i32>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Layer {
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A waypoint of the group it is in, its type like `Move` is the entity's `type`
+pub struct Waypoint {
+    /// `expCond`, the condition for the waypoint to complete
+    pub condition: Option<String>,
+    /// `expActiv`, run when the waypoint completes
+    pub on_activation: Option<String>,
+    /// `completitionRadius`, the distance from the position at which the waypoint completes
+    pub completion_radius: Option<f32>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Marker {
+    /// The variable name of the marker
+    pub name: Option<Spanned<String>>,
+    /// `markerType`, `RECTANGLE` or `ELLIPSE` for area markers, absent for icons
+    pub marker_type: Option<String>,
+    /// Half of the width and height of an area marker
+    pub size: Option<(f32, f32)>,
+    pub angle: Option<f32>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trigger {
+    pub condition: Option<String>,
+    /// `type` in the trigger attributes, like `SWITCH`, absent for `ACTIVATE`
+    pub activation_type: Option<String>,
+    pub server_only: Option<Spanned<i32>>,
+    pub interval: Option<Spanned<f32>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Logic {
+    pub area_is_rectangle: bool,
+    /// `areaSize` of area modules, `[width, height, length]`
+    pub area_size: Option<[f32; 3]>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+/// A connection in `Mission >> Connections >> Links`
+pub struct Connection {
+    pub item0: i32,
+    pub item1: i32,
+    /// `CustomData >> type`, like `WaypointActivation` or `Sync`
+    pub connection_type: Option<String>,
+    /// Span of the link's class name
+    pub span: Range<usize>,
+}
+
+impl Mission {
+    /// A mission from entities and connections that are already known
+    pub fn new(entities: Vec<Entity>, connections: Vec<Connection>) -> Self {
+        let mut mission = Self {
+            entities,
+            connections,
+            ids: HashMap::new(),
+        };
+        mission.index();
+        mission
+    }
+
+    /// Parse the entities and connections of a `mission.sqm`
+    pub fn parse(config: &Config) -> Self {
+        let mut mission = Self::default();
+        if let Some(entities) = get_class(config, "Mission.Entities") {
+            mission.parse_entities(entities, None, &[]);
+        }
+        mission.index();
+        if let Some(links) = get_class(config, "Mission.Connections.Links") {
+            for link in children(links) {
+                let (Some((item0, _)), Some((item1, _))) =
+                    (get_number(link, "item0"), get_number(link, "item1"))
+                else {
+                    continue;
+                };
+                mission.connections.push(Connection {
+                    item0,
+                    item1,
+                    connection_type: get_class(link, "CustomData")
+                        .and_then(|data| get_string(data, "type"))
+                        .map(|(value, _)| value.to_string()),
+                    span: name_span(link),
+                });
+            }
+        }
+        mission
+    }
+
+    /// Find an entity by ID
+    pub fn entity(&self, id: i32) -> Option<&Entity> {
+        self.ids.get(&id).map(|&index| &self.entities[index])
+    }
+
+    /// Index the entities by ID, the first entity wins when an ID is used twice
+    fn index(&mut self) {
+        self.ids.clear();
+        for (index, entity) in self.entities.iter().enumerate() {
+            if let Some(id) = entity.id {
+                self.ids.entry(id).or_insert(index);
+            }
+        }
+    }
+
+    fn parse_entities(&mut self, parent: &Class, group: Option<i32>, layers: &[String]) {
+        for class in children(parent) {
+            let Some((data_type, _)) = get_string(class, "dataType") else {
+                continue;
+            };
+            let entity = Entity::parse(class, data_type, group, layers);
+            let nested = get_class(class, "Entities");
+            let index = self.entities.len();
+            let id = entity.id;
+            self.entities.push(entity);
+            let Some(nested) = nested else {
+                continue;
+            };
+            match data_type {
+                "Group" => {
+                    self.parse_entities(nested, id, layers);
+                    let members = self.entities[index + 1..]
+                        .iter()
+                        .filter(|member| member.group == id && id.is_some());
+                    let ids = |kind: fn(&Kind) -> bool| {
+                        members
+                            .clone()
+                            .filter(|member| kind(&member.kind))
+                            .filter_map(|member| member.id)
+                            .collect()
+                    };
+                    self.entities[index].kind = Kind::Group(Group {
+                        units: ids(|kind| matches!(kind, Kind::Object(_))),
+                        waypoints: ids(|kind| matches!(kind, Kind::Waypoint(_))),
+                    });
+                }
+                "Layer" => {
+                    let mut layers = layers.to_vec();
+                    if let Kind::Layer(Layer { name: Some(name) }) = &self.entities[index].kind {
+                        layers.push(name.clone());
+                    }
+                    self.parse_entities(nested, group, &layers);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+impl Entity {
+    fn parse(class: &Class, data_type: &str, group: Option<i32>, layers: &[String]) -> Self {
+        let attributes = get_class(class, "Attributes")
+            .map(entries)
+            .unwrap_or_default();
+        let position_info = get_class(class, "PositionInfo");
+        let position = position_info
+            .and_then(|info| vector(info, "position"))
+            .or_else(|| vector(class, "position"));
+        let rotation = position_info.and_then(|info| vector(info, "angles"));
+        let kind = match data_type {
+            "Object" => Kind::Object(Object {
+                player: int(&attributes, "isPlayer") == Some(1),
+                playable: int(&attributes, "isPlayable") == Some(1),
+                description: attributes.get("description").and_then(|value| {
+                    Some(Spanned {
+                        value: value.value.as_str()?.to_string(),
+                        span: value.span.clone(),
+                    })
+                }),
+                crew: get_class(class, "CrewLinks").is_some(),
+            }),
+            "Group" => Kind::Group(Group {
+                units: Vec::new(),
+                waypoints: Vec::new(),
+            }),
+            "Layer" => Kind::Layer(Layer {
+                name: get_string(class, "name").map(|(name, _)| name.to_string()),
+            }),
+            "Waypoint" => Kind::Waypoint(Waypoint {
+                condition: get_string(class, "expCond").map(|(value, _)| value.to_string()),
+                on_activation: get_string(class, "expActiv").map(|(value, _)| value.to_string()),
+                completion_radius: float(class, "completitionRadius"),
+            }),
+            "Marker" => Kind::Marker(Marker {
+                name: spanned_string(class, "name"),
+                marker_type: get_string(class, "markerType").map(|(value, _)| value.to_string()),
+                size: float(class, "a").zip(float(class, "b")),
+                angle: float(class, "angle"),
+            }),
+            "Trigger" => Kind::Trigger(Trigger {
+                condition: attributes
+                    .get("condition")
+                    .and_then(|value| value.value.as_str())
+                    .map(str::to_string),
+                activation_type: attributes
+                    .get("type")
+                    .and_then(|value| value.value.as_str())
+                    .map(str::to_string),
+                server_only: attributes.get("isServerOnly").and_then(|value| {
+                    Some(Spanned {
+                        value: value.value.as_int()? as i32,
+                        span: value.span.clone(),
+                    })
+                }),
+                interval: attributes.get("triggerInterval").and_then(|value| {
+                    Some(Spanned {
+                        value: value.value.as_float()?,
+                        span: value.span.clone(),
+                    })
+                }),
+            }),
+            "Logic" => Kind::Logic(Logic {
+                area_is_rectangle: get_number(class, "areaIsRectangle")
+                    .is_some_and(|(value, _)| value == 1),
+                area_size: vector(class, "areaSize"),
+            }),
+            _ => Kind::Other,
+        };
+        Self {
+            id: get_number(class, "id").map(|(id, _)| id),
+            data_type: data_type.to_string(),
+            class_type: spanned_string(class, "type"),
+            side: spanned_string(class, "side"),
+            position,
+            rotation,
+            attributes,
+            custom_attributes: custom_attributes(class),
+            group,
+            layers: layers.to_vec(),
+            span: name_span(class),
+            kind,
+        }
+    }
+
+    /// Span of the entity's `type`, or of its class name when it has no type
+    pub fn type_span(&self) -> Range<usize> {
+        self.class_type
+            .as_ref()
+            .map_or(self.span.clone(), |class_type| class_type.span.clone())
+    }
+
+    /// Position on the map, `(x, y)`
+    pub fn position_2d(&self) -> Option<(f32, f32)> {
+        self.position.map(|[x, _, y]| (x, y))
+    }
+
+    /// Check if the entity is a playable unit
+    pub fn is_playable(&self) -> bool {
+        matches!(&self.kind, Kind::Object(object) if object.player || object.playable)
+    }
+
+    /// The value of a custom attribute, by property
+    pub fn custom_attribute(&self, property: &str) -> Option<&AttributeValue> {
+        self.custom_attributes
+            .get(property)
+            .map(|attribute| &attribute.value)
+    }
+}
+
+fn children(class: &Class) -> impl Iterator<Item = &Class> {
+    class.get_children().iter().filter_map(|property| {
+        if let Property::Class(class) = property {
+            Some(class)
+        } else {
+            None
+        }
+    })
+}
+
+fn name_span(class: &Class) -> Range<usize> {
+    class.name().map(|name| name.span.clone()).unwrap_or(0..0)
+}
+
+fn spanned_string(class: &Class, name: &str) -> Option<Spanned<String>> {
+    get_string(class, name).map(|(value, span)| Spanned {
+        value: value.to_string(),
+        span: span.clone(),
+    })
+}
+
+fn float(class: &Class, name: &str) -> Option<f32> {
+    match value(class, name)?.value {
+        AttributeValue::Int(value) => Some(value as f32),
+        AttributeValue::Float(value) => Some(value),
+        _ => None,
+    }
+}
+
+fn int(attributes: &HashMap<String, Spanned<AttributeValue>>, name: &str) -> Option<i64> {
+    attributes.get(name).and_then(|value| value.value.as_int())
+}
+
+fn vector(class: &Class, name: &str) -> Option<[f32; 3]> {
+    let (items, _) = get_array(class, name)?;
+    let mut vector = [0.0; 3];
+    for (i, item) in items.iter().take(3).enumerate() {
+        vector[i] = item_value(item)?.as_float()?;
+    }
+    Some(vector)
+}
+
+fn value(class: &Class, name: &str) -> Option<Spanned<AttributeValue>> {
+    class
+        .get_children()
+        .iter()
+        .find_map(|property| match property {
+            Property::Entry {
+                name: entry, value, ..
+            } if entry.as_str() == name => convert(value),
+            _ => None,
+        })
+}
+
+/// All entries of a class that have a value
+fn entries(class: &Class) -> HashMap<String, Spanned<AttributeValue>> {
+    class
+        .get_children()
+        .iter()
+        .filter_map(|property| match property {
+            Property::Entry { name, value, .. } => {
+                Some((name.as_str().to_string(), convert(value)?))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Values of `CustomAttributes >> AttributeN >> Value >> data >> value`, by property
+fn custom_attributes(class: &Class) -> HashMap<String, Spanned<AttributeValue>> {
+    let Some(custom) = get_class(class, "CustomAttributes") else {
+        return HashMap::new();
+    };
+    children(custom)
+        .filter_map(|attribute| {
+            let (property, _) = get_string(attribute, "property")?;
+            let value = value(get_class(attribute, "Value.data")?, "value")?;
+            Some((property.to_string(), value))
+        })
+        .collect()
+}
+
+fn convert(value: &Value) -> Option<Spanned<AttributeValue>> {
+    Some(match value {
+        Value::Str(value) => Spanned {
+            value: AttributeValue::Str(value.value().to_string()),
+            span: value.span().clone(),
+        },
+        Value::Number(number) => Spanned {
+            value: number_value(number),
+            span: number.span(),
+        },
+        Value::Array(array) => Spanned {
+            value: AttributeValue::Array(array.items().iter().filter_map(item_value).collect()),
+            span: array.span().clone(),
+        },
+        _ => return None,
+    })
+}
+
+fn item_value(item: &Item) -> Option<AttributeValue> {
+    Some(match item {
+        Item::Str(value) => AttributeValue::Str(value.value().to_string()),
+        Item::Number(number) => number_value(number),
+        Item::Array(items) => AttributeValue::Array(items.iter().filter_map(item_value).collect()),
+        _ => return None,
+    })
+}
+
+const fn number_value(number: &Number) -> AttributeValue {
+    match number {
+        Number::Int32 { value, .. } => AttributeValue::Int(*value as i64),
+        Number::Int64 { value, .. } => AttributeValue::Int(*value),
+        Number::Float32 { value, .. } => AttributeValue::Float(*value),
+    }
+}
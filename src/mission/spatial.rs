@@ -0,0 +1,209 @@
+use std::collections::HashMap;
+
+use super::model::{Entity, Mission};
+
+/// Width of a grid cell in meters, most proximity rules look within a few cells
+const CELL_SIZE: f32 = 50.0;
+
+/// Grid of the entities with a position, for distance queries on the map
+pub struct SpatialIndex<'a> {
+    mission: &'a Mission,
+    cells: HashMap<(i32, i32), Vec<usize>>,
+    /// Smallest and largest occupied cell on each axis, `None` when nothing has a position
+    bounds: Option<((i32, i32), (i32, i32))>,
+}
+
+impl<'a> SpatialIndex<'a> {
+    /// Index the entities of a mission by their position on the map
+    pub fn new(mission: &'a Mission) -> Self {
+        let mut cells: HashMap<(i32, i32), Vec<usize>> = HashMap::new();
+        for (i, entity) in mission.entities.iter().enumerate() {
+            if let Some(position) = entity.position_2d() {
+                cells.entry(cell(position)).or_default().push(i);
+            }
+        }
+        let bounds =
+            cells
+                .keys()
+                .fold(None, |bounds: Option<((i32, i32), (i32, i32))>, &(x, y)| {
+                    Some(match bounds {
+                        None => ((x, y), (x, y)),
+                        Some(((min_x, min_y), (max_x, max_y))) => {
+                            ((min_x.min(x), min_y.min(y)), (max_x.max(x), max_y.max(y)))
+                        }
+                    })
+                });
+        Self {
+            mission,
+            cells,
+            bounds,
+        }
+    }
+
+    /// The mission the index was built from
+    pub const fn mission(&self) -> &'a Mission {
+        self.mission
+    }
+
+    /// Entities with a position, in the order of the mission
+    pub fn positioned(&self) -> impl Iterator<Item = (&'a Entity, (f32, f32))> + 'a {
+        self.mission
+            .entities
+            .iter()
+            .filter_map(|entity| Some((entity, entity.position_2d()?)))
+    }
+
+    /// Entities within a radius of a point, closest first, with their distance
+    pub fn within(&self, center: (f32, f32), radius: f32) -> Vec<(&'a Entity, f32)> {
+        let Some(((min_x, min_y), (max_x, max_y))) = self.bounds else {
+            return Vec::new();
+        };
+        let (from_x, from_y) = cell((center.0 - radius, center.1 - radius));
+        let (to_x, to_y) = cell((center.0 + radius, center.1 + radius));
+        let (from_x, from_y) = (from_x.max(min_x), from_y.max(min_y));
+        let (to_x, to_y) = (to_x.min(max_x), to_y.min(max_y));
+        if from_x > to_x || from_y > to_y {
+            return Vec::new();
+        }
+        let area =
+            (i64::from(to_x) - i64::from(from_x) + 1) * (i64::from(to_y) - i64::from(from_y) + 1);
+        let mut found = Vec::new();
+        let mut visit = |indices: &Vec<usize>| {
+            for &i in indices {
+                let Some(position) = self.mission.entities[i].position_2d() else {
+                    continue;
+                };
+                let distance = distance(center, position);
+                if distance <= radius {
+                    found.push((i, distance));
+                }
+            }
+        };
+        if area > self.cells.len() as i64 {
+            // Fewer occupied cells than cells in range, visit the occupied ones
+            for (&(x, y), indices) in &self.cells {
+                if (from_x..=to_x).contains(&x) && (from_y..=to_y).contains(&y) {
+                    visit(indices);
+                }
+            }
+        } else {
+            for x in from_x..=to_x {
+                for y in from_y..=to_y {
+                    if let Some(indices) = self.cells.get(&(x, y)) {
+                        visit(indices);
+                    }
+                }
+            }
+        }
+        found.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+        found
+            .into_iter()
+            .map(|(i, distance)| (&self.mission.entities[i], distance))
+            .collect()
+    }
+
+    /// The closest entity to a point that matches a filter, with its distance
+    ///
+    /// Cells are searched in rings around the point, until no closer entity can be found
+    pub fn nearest(
+        &self,
+        center: (f32, f32),
+        filter: impl Fn(&Entity) -> bool,
+    ) -> Option<(&'a Entity, f32)> {
+        let bounds = self.bounds?;
+        let ((min_x, min_y), (max_x, max_y)) = bounds;
+        let center_cell = cell(center);
+        let (center_x, center_y) = (i64::from(center_cell.0), i64::from(center_cell.1));
+        // Rings before the first one miss every occupied cell, rings after the last one too
+        let first = [
+            i64::from(min_x) - center_x,
+            center_x - i64::from(max_x),
+            i64::from(min_y) - center_y,
+            center_y - i64::from(max_y),
+        ]
+        .into_iter()
+        .fold(0, i64::max);
+        let last = [
+            center_x - i64::from(min_x),
+            i64::from(max_x) - center_x,
+            center_y - i64::from(min_y),
+            i64::from(max_y) - center_y,
+        ]
+        .into_iter()
+        .fold(0, i64::max);
+        let mut best: Option<(usize, f32)> = None;
+        for ring in first..=last {
+            for key in ring_cells((center_x, center_y), ring, bounds) {
+                let Some(indices) = self.cells.get(&key) else {
+                    continue;
+                };
+                for &i in indices {
+                    let entity = &self.mission.entities[i];
+                    let Some(position) = entity.position_2d() else {
+                        continue;
+                    };
+                    let distance = distance(center, position);
+                    if best.is_none_or(|(_, best)| distance < best) && filter(entity) {
+                        best = Some((i, distance));
+                    }
+                }
+            }
+            // Entities in the next ring are at least this far away
+            if best.is_some_and(|(_, best)| best <= ring as f32 * CELL_SIZE) {
+                break;
+            }
+        }
+        best.map(|(i, distance)| (&self.mission.entities[i], distance))
+    }
+}
+
+/// The cells on the edge of a square `ring` cells away from `center`, inside of `bounds`
+fn ring_cells(
+    center: (i64, i64),
+    ring: i64,
+    ((min_x, min_y), (max_x, max_y)): ((i32, i32), (i32, i32)),
+) -> Vec<(i32, i32)> {
+    let (min_x, min_y, max_x, max_y) = (
+        i64::from(min_x),
+        i64::from(min_y),
+        i64::from(max_x),
+        i64::from(max_y),
+    );
+    let mut cells = Vec::new();
+    let mut push = |x: i64, y: i64| {
+        if let (Ok(x), Ok(y)) = (i32::try_from(x), i32::try_from(y)) {
+            cells.push((x, y));
+        }
+    };
+    if ring == 0 {
+        push(center.0, center.1);
+        return cells;
+    }
+    for y in [center.1 - ring, center.1 + ring] {
+        if (min_y..=max_y).contains(&y) {
+            for x in (center.0 - ring).max(min_x)..=(center.0 + ring).min(max_x) {
+                push(x, y);
+            }
+        }
+    }
+    for x in [center.0 - ring, center.0 + ring] {
+        if (min_x..=max_x).contains(&x) {
+            for y in (center.1 - ring + 1).max(min_y)..=(center.1 + ring - 1).min(max_y) {
+                push(x, y);
+            }
+        }
+    }
+    cells
+}
+
+/// Distance between two points on the map
+pub fn distance(a: (f32, f32), b: (f32, f32)) -> f32 {
+    (a.0 - b.0).hypot(a.1 - b.1)
+}
+
+fn cell(position: (f32, f32)) -> (i32, i32) {
+    (
+        (position.0 / CELL_SIZE).floor() as i32,
+        (position.1 / CELL_SIZE).floor() as i32,
+    )
+}
@@ -0,0 +1,6 @@
+use crate::report::Report;
+
+/// Render a report as JSON, following `schema/report.schema.json`
+pub fn render(report: &Report) -> String {
+    serde_json::to_string_pretty(report).expect("report is valid JSON")
+}
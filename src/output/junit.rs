@@ -0,0 +1,146 @@
+use std::fmt::Write;
+
+use crate::{
+    annotation::{Annotation, Level},
+    report::{MissionReport, Report},
+};
+
+/// Render a report as JUnit XML
+///
+/// Each mission is a testsuite and each rule is a testcase. Rules with errors fail,
+/// rules with only warnings are skipped, and all findings are listed in `system-out`.
+pub fn render(report: &Report) -> String {
+    let mut out = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let suites = report.missions.iter().map(suite).collect::<Vec<_>>();
+    let tests = suites.iter().map(|s| s.tests).sum::<usize>();
+    let failures = suites.iter().map(|s| s.failures).sum::<usize>();
+    let skipped = suites.iter().map(|s| s.skipped).sum::<usize>();
+    writeln!(
+        out,
+        "<testsuites name=\"missionreviewer\" tests=\"{}\" failures=\"{}\" skipped=\"{}\">",
+        tests, failures, skipped
+    )
+    .expect("write to string");
+    for suite in suites {
+        out.push_str(&suite.xml);
+    }
+    out.push_str("</testsuites>\n");
+    out
+}
+
+struct Suite {
+    tests: usize,
+    failures: usize,
+    skipped: usize,
+    xml: String,
+}
+
+fn suite(mission: &MissionReport) -> Suite {
+    let mut rules = mission.checks.clone();
+    for annotation in &mission.annotations {
+        if !rules.contains(&annotation.rule) {
+            rules.push(annotation.rule.clone());
+        }
+    }
+    let mut failures = 0;
+    let mut skipped = 0;
+    let mut cases = String::new();
+    for rule in &rules {
+        let findings = mission
+            .annotations
+            .iter()
+            .filter(|annotation| &annotation.rule == rule)
+            .collect::<Vec<_>>();
+        let errors = findings
+            .iter()
+            .filter(|annotation| annotation.level == Level::Error)
+            .count();
+        let warnings = findings
+            .iter()
+            .filter(|annotation| annotation.level == Level::Warning)
+            .count();
+        let details = findings
+            .iter()
+            .map(|annotation| describe(annotation))
+            .collect::<String>();
+        write!(
+            cases,
+            "    <testcase classname=\"{}\" name=\"{}\"",
+            escape(&mission.path),
+            escape(rule)
+        )
+        .expect("write to string");
+        if findings.is_empty() {
+            cases.push_str("/>\n");
+            continue;
+        }
+        cases.push_str(">\n");
+        if errors != 0 {
+            failures += 1;
+            writeln!(
+                cases,
+                "      <failure message=\"{} error(s)\" type=\"{}\">{}</failure>",
+                errors,
+                escape(rule),
+                escape(&details)
+            )
+            .expect("write to string");
+        } else if warnings != 0 {
+            skipped += 1;
+            writeln!(
+                cases,
+                "      <skipped message=\"{} warning(s)\"/>",
+                warnings
+            )
+            .expect("write to string");
+        }
+        writeln!(cases, "      <system-out>{}</system-out>", escape(&details))
+            .expect("write to string");
+        cases.push_str("    </testcase>\n");
+    }
+    let mut xml = String::new();
+    writeln!(
+        xml,
+        "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" skipped=\"{}\">",
+        escape(&mission.path),
+        rules.len(),
+        failures,
+        skipped
+    )
+    .expect("write to string");
+    xml.push_str(&cases);
+    xml.push_str("  </testsuite>\n");
+    Suite {
+        tests: rules.len(),
+        failures,
+        skipped,
+        xml,
+    }
+}
+
+fn describe(annotation: &Annotation) -> String {
+    format!(
+        "{}: {}: {} ({}:{}:{})\n",
+        annotation.level,
+        annotation.title,
+        annotation.message,
+        annotation.path,
+        annotation.start_line,
+        annotation.start_column
+    )
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
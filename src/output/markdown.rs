@@ -0,0 +1,121 @@
+use std::fmt::Write;
+
+use crate::{
+    annotation::{Annotation, Level},
+    report::{MissionReport, Report},
+    rules,
+};
+
+/// Render a report as a markdown summary
+///
+/// A table with a row per mission, followed by the findings of each mission in a
+/// collapsible section. Suitable for `$GITHUB_STEP_SUMMARY` or a pull request comment.
+pub fn render(report: &Report) -> String {
+    let mut out = String::from("## Mission Reviewer\n\n");
+    if report.missions.is_empty() {
+        out.push_str("No missions were reviewed.\n");
+        return out;
+    }
+    let count = |level| report.annotations().filter(|a| a.level == level).count();
+    writeln!(
+        out,
+        "{} error(s), {} warning(s), {} notice(s) in {} mission(s)\n",
+        count(Level::Error),
+        count(Level::Warning),
+        count(Level::Notice),
+        report.missions.len(),
+    )
+    .expect("write to string");
+    out.push_str("| | Mission | Map | Players | Type | Errors | Warnings |\n");
+    out.push_str("|---|---|---|---:|---|---:|---:|\n");
+    for mission in &report.missions {
+        let errors = count_level(mission, Level::Error);
+        let warnings = count_level(mission, Level::Warning);
+        writeln!(
+            out,
+            "| {} | {} | {} | {} | {} | {} | {} |",
+            if errors > 0 {
+                "❌"
+            } else if warnings > 0 {
+                "⚠️"
+            } else {
+                "✅"
+            },
+            mission_name(mission),
+            escape(mission.map.as_deref().unwrap_or("-")),
+            mission
+                .player_count
+                .map_or_else(|| "-".to_string(), |count| count.to_string()),
+            mission.synixe_type.map_or("-", rules::type_name),
+            errors,
+            warnings,
+        )
+        .expect("write to string");
+    }
+    for mission in &report.missions {
+        if mission.annotations.is_empty() {
+            continue;
+        }
+        writeln!(
+            out,
+            "\n<details>\n<summary>{} - {} finding(s)</summary>\n",
+            escape(mission.name.as_deref().unwrap_or(&mission.path)),
+            mission.annotations.len(),
+        )
+        .expect("write to string");
+        for annotation in &mission.annotations {
+            out.push_str(&finding(annotation));
+        }
+        out.push_str("\n</details>\n");
+    }
+    out
+}
+
+fn count_level(mission: &MissionReport, level: Level) -> usize {
+    mission
+        .annotations
+        .iter()
+        .filter(|annotation| annotation.level == level)
+        .count()
+}
+
+fn mission_name(mission: &MissionReport) -> String {
+    mission.name.as_ref().map_or_else(
+        || format!("`{}`", escape(&mission.path)),
+        |name| format!("{}<br>`{}`", escape(name), escape(&mission.path)),
+    )
+}
+
+fn finding(annotation: &Annotation) -> String {
+    let mut out = format!(
+        "- **{}** `{}` {}: {} (`{}:{}:{}`)\n",
+        annotation.level,
+        annotation.code,
+        escape(rules::code(&annotation.code).map_or(annotation.title.as_str(), |code| code.title)),
+        annotation.message.replace('\n', " "),
+        annotation.path,
+        annotation.start_line,
+        annotation.start_column,
+    );
+    for related in &annotation.related {
+        writeln!(
+            out,
+            "  - {} (`{}:{}:{}`)",
+            related.message.replace('\n', " "),
+            related.path,
+            related.start_line,
+            related.start_column,
+        )
+        .expect("write to string");
+    }
+    out
+}
+
+/// Escape characters that would break a table cell or be read as HTML
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('|', "\\|")
+        .replace('\n', " ")
+}
@@ -0,0 +1,5 @@
+pub mod json;
+pub mod junit;
+pub mod markdown;
+pub mod pretty;
+pub mod sarif;
@@ -0,0 +1,232 @@
+use std::{collections::HashMap, fmt::Write};
+
+use crate::{
+    annotation::{Annotation, Level},
+    report::{MissionReport, Report},
+    rules,
+};
+
+/// Most lines of a multi-line span that are shown
+const MAX_SNIPPET_LINES: usize = 6;
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const DIM: &str = "\x1b[2m";
+const BLUE: &str = "\x1b[34m";
+
+/// Render a report for a terminal, grouped by mission with source snippets
+pub fn render(report: &Report, color: bool) -> String {
+    let mut out = String::new();
+    let mut sources = Sources::default();
+    for mission in &report.missions {
+        render_mission(&mut out, &mut sources, mission, color);
+    }
+    let count = |level| report.annotations().filter(|a| a.level == level).count();
+    writeln!(
+        out,
+        "{}{} error(s), {} warning(s), {} notice(s) in {} mission(s){}",
+        paint(BOLD, color),
+        count(Level::Error),
+        count(Level::Warning),
+        count(Level::Notice),
+        report.missions.len(),
+        paint(RESET, color),
+    )
+    .expect("write to string");
+    out
+}
+
+/// Render the findings of a single mission
+pub fn render_mission(
+    out: &mut String,
+    sources: &mut Sources,
+    mission: &MissionReport,
+    color: bool,
+) {
+    let mut details = Vec::new();
+    if let Some(map) = &mission.map {
+        details.push(map.clone());
+    }
+    if let Some(player_count) = mission.player_count {
+        details.push(format!("{} players", player_count));
+    }
+    if let Some(synixe_type) = mission.synixe_type {
+        details.push(rules::type_name(synixe_type).to_string());
+    }
+    writeln!(
+        out,
+        "{}{}{} {}({}){}",
+        paint(BOLD, color),
+        mission.path,
+        paint(RESET, color),
+        paint(DIM, color),
+        details.join(", "),
+        paint(RESET, color),
+    )
+    .expect("write to string");
+    if mission.annotations.is_empty() {
+        writeln!(out, "  no findings\n").expect("write to string");
+        return;
+    }
+    for annotation in &mission.annotations {
+        render_annotation(out, sources, annotation, color);
+    }
+}
+
+fn render_annotation(
+    out: &mut String,
+    sources: &mut Sources,
+    annotation: &Annotation,
+    color: bool,
+) {
+    writeln!(
+        out,
+        "{}{}{}[{}]{}{}: {}{}",
+        paint(level_color(annotation.level), color),
+        paint(BOLD, color),
+        annotation.level,
+        annotation.code,
+        paint(RESET, color),
+        paint(BOLD, color),
+        annotation.message,
+        paint(RESET, color),
+    )
+    .expect("write to string");
+    let file_level = annotation.start_line <= 1
+        && annotation.end_line <= 1
+        && annotation.start_column <= 1
+        && annotation.end_column <= 1;
+    if file_level {
+        writeln!(
+            out,
+            "  {}-->{} {}",
+            paint(BLUE, color),
+            paint(RESET, color),
+            annotation.path
+        )
+        .expect("write to string");
+        render_related(out, annotation, color);
+        out.push('\n');
+        return;
+    }
+    writeln!(
+        out,
+        "  {}-->{} {}:{}:{}",
+        paint(BLUE, color),
+        paint(RESET, color),
+        annotation.path,
+        annotation.start_line,
+        annotation.start_column
+    )
+    .expect("write to string");
+    let Some(lines) = sources.lines(&annotation.path) else {
+        render_related(out, annotation, color);
+        out.push('\n');
+        return;
+    };
+    let last = annotation
+        .end_line
+        .max(annotation.start_line)
+        .min(annotation.start_line + MAX_SNIPPET_LINES - 1);
+    let width = last.to_string().len();
+    writeln!(
+        out,
+        "{} {:width$} |{}",
+        paint(BLUE, color),
+        "",
+        paint(RESET, color)
+    )
+    .expect("write to string");
+    for number in annotation.start_line..=last {
+        let Some(line) = lines.get(number - 1) else {
+            break;
+        };
+        writeln!(
+            out,
+            "{} {:>width$} |{} {}",
+            paint(BLUE, color),
+            number,
+            paint(RESET, color),
+            line
+        )
+        .expect("write to string");
+    }
+    if annotation.start_line == annotation.end_line {
+        let start = annotation.start_column.max(1);
+        let length = annotation.end_column.saturating_sub(start).max(1);
+        // Keep tabs so the carets line up with the indentation of the line
+        let padding = lines
+            .get(annotation.start_line - 1)
+            .map(|line| {
+                line.chars()
+                    .chain(std::iter::repeat(' '))
+                    .take(start - 1)
+                    .map(|c| if c == '\t' { '\t' } else { ' ' })
+                    .collect::<String>()
+            })
+            .unwrap_or_default();
+        writeln!(
+            out,
+            "{} {:width$} |{} {}{}{}{}",
+            paint(BLUE, color),
+            "",
+            paint(RESET, color),
+            padding,
+            paint(level_color(annotation.level), color),
+            "^".repeat(length),
+            paint(RESET, color),
+        )
+        .expect("write to string");
+    }
+    render_related(out, annotation, color);
+    out.push('\n');
+}
+
+fn render_related(out: &mut String, annotation: &Annotation, color: bool) {
+    for related in &annotation.related {
+        writeln!(
+            out,
+            "  {}= note:{} {} at {}:{}:{}",
+            paint(BLUE, color),
+            paint(RESET, color),
+            related.message,
+            related.path,
+            related.start_line,
+            related.start_column
+        )
+        .expect("write to string");
+    }
+}
+
+#[derive(Default)]
+/// Cache of source files, split into lines
+pub struct Sources(HashMap<String, Option<Vec<String>>>);
+
+impl Sources {
+    fn lines(&mut self, path: &str) -> Option<&Vec<String>> {
+        self.0
+            .entry(path.to_string())
+            .or_insert_with(|| {
+                std::fs::read_to_string(path)
+                    .ok()
+                    .map(|content| content.lines().map(str::to_string).collect())
+            })
+            .as_ref()
+    }
+}
+
+const fn level_color(level: Level) -> &'static str {
+    match level {
+        Level::Notice => "\x1b[36m",
+        Level::Warning => "\x1b[33m",
+        Level::Error => "\x1b[31m",
+    }
+}
+
+const fn paint(code: &'static str, color: bool) -> &'static str {
+    if color {
+        code
+    } else {
+        ""
+    }
+}
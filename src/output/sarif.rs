@@ -0,0 +1,86 @@
+use serde_json::json;
+
+use crate::{
+    annotation::{Annotation, Level},
+    rules::{self, RULES},
+};
+
+/// Render annotations as a SARIF 2.1.0 log
+///
+/// <https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html>
+pub fn render(messages: &[Annotation]) -> String {
+    let codes = RULES
+        .iter()
+        .flat_map(|rule| rule.codes.iter().copied())
+        .collect::<Vec<_>>();
+    let log = json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "missionreviewer",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": "https://github.com/SynixeContractors/MissionReviewer",
+                    "rules": codes.iter().map(|code| json!({
+                        "id": code.code,
+                        "name": code.slug,
+                        "shortDescription": { "text": code.title },
+                        "fullDescription": { "text": code.explanation },
+                        "defaultConfiguration": { "level": level(code.level) },
+                        "properties": { "rule": code.rule },
+                    })).collect::<Vec<_>>(),
+                },
+            },
+            "results": messages.iter().map(|message| {
+                let mut result = json!({
+                    "ruleId": message.code,
+                    "level": level(message.level),
+                    "message": { "text": message.message },
+                    "locations": [{
+                        "physicalLocation": {
+                            "artifactLocation": { "uri": message.path.replace('\\', "/") },
+                            "region": {
+                                "startLine": message.start_line.max(1),
+                                "startColumn": message.start_column.max(1),
+                                "endLine": message.end_line.max(message.start_line).max(1),
+                                "endColumn": message.end_column.max(1),
+                            },
+                        },
+                    }],
+                });
+                if !message.related.is_empty() {
+                    result["relatedLocations"] = message.related.iter().enumerate().map(|(id, related)| json!({
+                        "id": id,
+                        "message": { "text": related.message },
+                        "physicalLocation": {
+                            "artifactLocation": { "uri": related.path.replace('\\', "/") },
+                            "region": {
+                                "startLine": related.start_line.max(1),
+                                "startColumn": related.start_column.max(1),
+                                "endLine": related.end_line.max(related.start_line).max(1),
+                                "endColumn": related.end_column.max(1),
+                            },
+                        },
+                    })).collect();
+                }
+                if let Some(index) = codes.iter().position(|code| code.code == message.code) {
+                    result["ruleIndex"] = json!(index);
+                }
+                if let Some(code) = rules::code(&message.code) {
+                    result["properties"] = json!({ "title": code.title, "rule": code.rule });
+                }
+                result
+            }).collect::<Vec<_>>(),
+        }],
+    });
+    serde_json::to_string_pretty(&log).expect("SARIF log is valid JSON")
+}
+
+const fn level(level: Level) -> &'static str {
+    match level {
+        Level::Notice => "note",
+        Level::Warning => "warning",
+        Level::Error => "error",
+    }
+}
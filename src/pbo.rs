@@ -0,0 +1,211 @@
+use std::{
+    hash::{DefaultHasher, Hash, Hasher},
+    io::Read,
+    path::{Component, Path, PathBuf},
+};
+
+use hemtt_pbo::{ReadablePbo, WritablePbo};
+
+use crate::{mission, report::MissionReport, settings::Settings};
+
+/// Folders that are only used while developing a mission
+const DEV_FOLDERS: [&str; 4] = [".git", ".github", ".vscode", ".idea"];
+
+/// Files that are only used while developing a mission, compared case insensitively
+const DEV_FILES: [&str; 6] = [
+    "readme.txt",
+    "readme.md",
+    ".gitignore",
+    ".gitattributes",
+    "desktop.ini",
+    "thumbs.db",
+];
+
+/// Check if a path is a packed mission
+pub fn is_archive(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("pbo"))
+}
+
+/// Split command line arguments into archives and filters
+///
+/// An argument that is a `.pbo` file, or a folder containing `.pbo` files, is reviewed as
+/// archives, every other argument stays a filter for the missions in the roots
+pub fn archives(args: &[String]) -> (Vec<PathBuf>, Vec<String>) {
+    let mut archives = Vec::new();
+    let mut filters = Vec::new();
+    for arg in args {
+        let path = Path::new(arg);
+        if is_archive(path) {
+            archives.push(path.to_path_buf());
+            continue;
+        }
+        let mut found = path
+            .read_dir()
+            .map(|entries| {
+                entries
+                    .filter_map(Result::ok)
+                    .map(|entry| entry.path())
+                    .filter(|path| is_archive(path))
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+        if found.is_empty() {
+            filters.push(arg.clone());
+        } else {
+            found.sort();
+            archives.extend(found);
+        }
+    }
+    (archives, filters)
+}
+
+/// Review a packed mission
+///
+/// The preprocessor only reads files through a workspace, and a workspace can only be built
+/// from folders on disk, so the archive is unpacked to a temporary folder named like the archive
+/// and removed after the review. Roots and overrides are matched against the archive path.
+/// Annotations point at `archive.pbo/<inner path>` and have no fixes.
+pub fn check(archive: &Path, settings: &Settings) -> Result<MissionReport, String> {
+    let name = archive
+        .file_stem()
+        .ok_or_else(|| format!("{} has no file name", archive.display()))?;
+    let mut hasher = DefaultHasher::new();
+    archive.hash(&mut hasher);
+    let temp = std::env::temp_dir().join(format!(
+        "missionreviewer-{}-{:x}",
+        std::process::id(),
+        hasher.finish()
+    ));
+    let dir = temp.join(name);
+    let result = unpack(archive, &dir).and_then(|()| mission::check_at(&dir, archive, settings));
+    let _ = std::fs::remove_dir_all(&temp);
+    let mut report = result?;
+    let unpacked = dir.display().to_string();
+    let archive_path = archive.display().to_string();
+    report.path.clone_from(&archive_path);
+    for annotation in &mut report.annotations {
+        annotation.path = remap(&annotation.path, &unpacked, &archive_path);
+        for related in &mut annotation.related {
+            related.path = remap(&related.path, &unpacked, &archive_path);
+        }
+        annotation.fix = None;
+    }
+    Ok(report)
+}
+
+/// Point a path in the unpacked folder at the archive, `archive.pbo/<inner path>`
+fn remap(path: &str, unpacked: &str, archive: &str) -> String {
+    let Some(inner) = path.strip_prefix(unpacked) else {
+        return path.to_string();
+    };
+    format!(
+        "{}/{}",
+        archive,
+        inner.replace('\\', "/").trim_start_matches('/')
+    )
+    .trim_end_matches('/')
+    .to_string()
+}
+
+/// Unpack every file of an archive into a folder
+pub fn unpack(archive: &Path, dir: &Path) -> Result<(), String> {
+    let file = std::fs::File::open(archive)
+        .map_err(|e| format!("failed to read {}: {}", archive.display(), e))?;
+    let mut pbo = ReadablePbo::from(file)
+        .map_err(|e| format!("{} is not a valid PBO: {}", archive.display(), e))?;
+    for header in pbo.files() {
+        let inner = header.filename().replace('\\', "/");
+        let relative = Path::new(&inner);
+        if !relative
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)))
+        {
+            continue;
+        }
+        let Some(mut file) = pbo
+            .file(header.filename())
+            .map_err(|e| format!("failed to read {} from {}: {}", inner, archive.display(), e))?
+        else {
+            continue;
+        };
+        let mut content = Vec::new();
+        file.read_to_end(&mut content)
+            .map_err(|e| format!("failed to read {} from {}: {}", inner, archive.display(), e))?;
+        let path = dir.join(relative);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("failed to create {}: {}", parent.display(), e))?;
+        }
+        std::fs::write(&path, content)
+            .map_err(|e| format!("failed to write {}: {}", path.display(), e))?;
+    }
+    Ok(())
+}
+
+/// Check if a file, relative to the mission folder, is only used while developing the mission
+///
+/// Covers version control and editor folders, readmes and editor backups
+pub fn is_dev_file(relative: &Path) -> bool {
+    if relative.components().any(|component| {
+        DEV_FOLDERS
+            .iter()
+            .any(|folder| component.as_os_str() == *folder)
+    }) {
+        return true;
+    }
+    let name = relative
+        .file_name()
+        .map(|name| name.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    DEV_FILES.contains(&name.as_str()) || name.ends_with(".bak") || name.ends_with('~')
+}
+
+/// Pack a mission folder into an archive, returning the number of files packed
+///
+/// Development files are left out and the `prefix` header is set to the folder name
+pub fn pack(dir: &Path, output: &Path) -> Result<usize, String> {
+    let name = dir
+        .file_name()
+        .ok_or_else(|| format!("{} has no folder name", dir.display()))?
+        .to_string_lossy()
+        .to_string();
+    let mut pbo = WritablePbo::new();
+    let mut count = 0;
+    for entry in walkdir::WalkDir::new(dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|entry| {
+            entry
+                .path()
+                .strip_prefix(dir)
+                .map(|relative| !is_dev_file(relative))
+                .unwrap_or_default()
+        })
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+    {
+        let relative = entry
+            .path()
+            .strip_prefix(dir)
+            .expect("entry is in the mission folder");
+        let inner = relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("\\");
+        let file = std::fs::File::open(entry.path())
+            .map_err(|e| format!("failed to read {}: {}", entry.path().display(), e))?;
+        pbo.add_file(inner, file)
+            .map_err(|e| format!("failed to add {}: {}", entry.path().display(), e))?;
+        count += 1;
+    }
+    pbo.add_property("prefix", name.as_str());
+    let mut file = std::fs::File::create(output)
+        .map_err(|e| format!("failed to create {}: {}", output.display(), e))?;
+    pbo.write(&mut file, true)
+        .map_err(|e| format!("failed to write {}: {}", output.display(), e))?;
+    Ok(count)
+}
@@ -0,0 +1,184 @@
+use std::fmt::Write;
+
+/// Signature at the start of a binarized (rapified) config
+///
+/// <https://community.bistudio.com/wiki/raP_File_Format_-_OFP>
+pub const SIGNATURE: &[u8; 4] = b"\0raP";
+
+/// Deepest class nesting that is decoded, guards against offsets that loop
+const MAX_DEPTH: usize = 64;
+
+/// Check if the content of a file is a rapified config
+pub fn is_rapified(bytes: &[u8]) -> bool {
+    bytes.starts_with(SIGNATURE)
+}
+
+/// Decode a rapified config into config text, indented with tabs like Eden writes it
+pub fn derapify(bytes: &[u8]) -> Result<String, String> {
+    if !is_rapified(bytes) {
+        return Err("missing raP signature".to_string());
+    }
+    let mut reader = Reader { bytes, pos: 4 };
+    if reader.u32()? != 0 || reader.u32()? != 8 {
+        return Err("unsupported raP header".to_string());
+    }
+    // Offset of the enums, which missions do not use
+    reader.u32()?;
+    let mut out = String::new();
+    reader.class_body(&mut out, 0)?;
+    Ok(out)
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn take(&mut self, len: usize) -> Result<&[u8], String> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| format!("unexpected end of file at {}", self.pos))?;
+        let bytes = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        Ok(u32::from_le_bytes(
+            self.take(4)?.try_into().expect("4 bytes"),
+        ))
+    }
+
+    fn i32(&mut self) -> Result<i32, String> {
+        Ok(i32::from_le_bytes(
+            self.take(4)?.try_into().expect("4 bytes"),
+        ))
+    }
+
+    fn i64(&mut self) -> Result<i64, String> {
+        Ok(i64::from_le_bytes(
+            self.take(8)?.try_into().expect("8 bytes"),
+        ))
+    }
+
+    fn f32(&mut self) -> Result<f32, String> {
+        Ok(f32::from_le_bytes(
+            self.take(4)?.try_into().expect("4 bytes"),
+        ))
+    }
+
+    /// Variable length integer, 7 bits per byte with the high bit set on all but the last
+    fn compressed(&mut self) -> Result<usize, String> {
+        let mut value = 0;
+        for shift in (0..35).step_by(7) {
+            let byte = self.u8()?;
+            value |= usize::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(format!("invalid compressed integer at {}", self.pos))
+    }
+
+    fn asciiz(&mut self) -> Result<String, String> {
+        let len = self
+            .bytes
+            .get(self.pos..)
+            .unwrap_or_default()
+            .iter()
+            .position(|byte| *byte == 0)
+            .ok_or_else(|| format!("unterminated string at {}", self.pos))?;
+        let value = String::from_utf8_lossy(self.take(len)?).into_owned();
+        self.pos += 1;
+        Ok(value)
+    }
+
+    fn class_body(&mut self, out: &mut String, depth: usize) -> Result<(), String> {
+        if depth > MAX_DEPTH {
+            return Err("classes are nested too deep".to_string());
+        }
+        let indent = "\t".repeat(depth);
+        // Parent class, missions only inherit in external classes
+        self.asciiz()?;
+        let entries = self.compressed()?;
+        for _ in 0..entries {
+            match self.u8()? {
+                0 => {
+                    let name = self.asciiz()?;
+                    let offset = self.u32()? as usize;
+                    let resume = self.pos;
+                    self.pos = offset;
+                    writeln!(out, "{}class {}\n{}{{", indent, name, indent)
+                        .expect("write to string");
+                    self.class_body(out, depth + 1)?;
+                    writeln!(out, "{}}};", indent).expect("write to string");
+                    self.pos = resume;
+                }
+                1 => {
+                    let kind = self.u8()?;
+                    let name = self.asciiz()?;
+                    let value = self.scalar(kind)?;
+                    writeln!(out, "{}{}={};", indent, name, value).expect("write to string");
+                }
+                2 => {
+                    let name = self.asciiz()?;
+                    let value = self.array(depth)?;
+                    writeln!(out, "{}{}[]={};", indent, name, value).expect("write to string");
+                }
+                3 => {
+                    let name = self.asciiz()?;
+                    writeln!(out, "{}class {};", indent, name).expect("write to string");
+                }
+                4 => {
+                    let name = self.asciiz()?;
+                    writeln!(out, "{}delete {};", indent, name).expect("write to string");
+                }
+                5 => {
+                    self.u32()?;
+                    let name = self.asciiz()?;
+                    let value = self.array(depth)?;
+                    writeln!(out, "{}{}[]+={};", indent, name, value).expect("write to string");
+                }
+                kind => return Err(format!("unknown entry type {} at {}", kind, self.pos - 1)),
+            }
+        }
+        Ok(())
+    }
+
+    fn scalar(&mut self, kind: u8) -> Result<String, String> {
+        Ok(match kind {
+            0 => format!("\"{}\"", self.asciiz()?.replace('"', "\"\"")),
+            // Debug is the shortest text that parses back to the same `f32`, and unlike Display
+            // it keeps a decimal point or exponent, so `1.0` is not read back as an integer
+            1 => format!("{:?}", self.f32()?),
+            2 => self.i32()?.to_string(),
+            4 => self.asciiz()?,
+            6 => self.i64()?.to_string(),
+            kind => return Err(format!("unknown value type {} at {}", kind, self.pos)),
+        })
+    }
+
+    fn array(&mut self, depth: usize) -> Result<String, String> {
+        if depth > MAX_DEPTH {
+            return Err("arrays are nested too deep".to_string());
+        }
+        let items = self.compressed()?;
+        let mut values = Vec::with_capacity(items.min(self.bytes.len()));
+        for _ in 0..items {
+            let kind = self.u8()?;
+            values.push(if kind == 3 {
+                self.array(depth + 1)?
+            } else {
+                self.scalar(kind)?
+            });
+        }
+        Ok(format!("{{{}}}", values.join(",")))
+    }
+}
@@ -0,0 +1,101 @@
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::annotation::{Annotation, Level};
+
+/// Version of the report schema, `schema/report.schema.json`
+///
+/// Bumped when a field is removed or changes meaning, new fields can be added without a bump
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Exit code when a finding is at or above the `--fail-on` level
+pub const EXIT_FINDINGS: i32 = 1;
+/// Exit code when a mission could not be parsed, takes precedence over [`EXIT_FINDINGS`]
+pub const EXIT_PARSE: i32 = 3;
+/// Exit code when the configuration could not be loaded
+pub const EXIT_CONFIG: i32 = 4;
+
+#[derive(Debug, Clone, Serialize)]
+/// Results of a review
+pub struct Report {
+    /// Version of the report schema
+    pub schema_version: u32,
+    /// Reviewed missions, sorted by path
+    pub missions: Vec<MissionReport>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+/// Results of reviewing a single mission
+pub struct MissionReport {
+    /// The mission folder
+    pub path: String,
+    /// `OnLoadName` of the mission, when `description.ext` could be read
+    pub name: Option<String>,
+    /// `synixe_template` of the mission, when `description.ext` could be read
+    pub template_version: Option<u8>,
+    /// `synixe_type` of the mission, when `description.ext` could be read
+    pub synixe_type: Option<i32>,
+    /// The map, taken from the extension of the mission folder
+    pub map: Option<String>,
+    /// Number of playable units, when `mission.sqm` could be read
+    pub player_count: Option<usize>,
+    /// Rule IDs that were run
+    pub checks: Vec<String>,
+    /// Findings in the mission
+    pub annotations: Vec<Annotation>,
+}
+
+impl Report {
+    pub fn new(mut missions: Vec<MissionReport>) -> Self {
+        missions.sort_by(|a, b| a.path.cmp(&b.path));
+        Self {
+            schema_version: SCHEMA_VERSION,
+            missions,
+        }
+    }
+
+    /// All findings of all missions
+    pub fn annotations(&self) -> impl Iterator<Item = &Annotation> {
+        self.missions
+            .iter()
+            .flat_map(|mission| mission.annotations.iter())
+    }
+
+    /// The exit code for the report, `0` when no mission failed and nothing is at or above `fail_on`
+    pub fn exit_code(&self, fail_on: Level) -> i32 {
+        if self.missions.iter().any(|mission| !mission.parsed()) {
+            EXIT_PARSE
+        } else if self
+            .annotations()
+            .any(|annotation| annotation.level >= fail_on)
+        {
+            EXIT_FINDINGS
+        } else {
+            0
+        }
+    }
+}
+
+impl MissionReport {
+    pub fn new(dir: &Path) -> Self {
+        Self {
+            path: dir.display().to_string(),
+            name: None,
+            template_version: None,
+            synixe_type: None,
+            map: dir
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .map(str::to_string),
+            player_count: None,
+            checks: Vec::new(),
+            annotations: Vec::new(),
+        }
+    }
+
+    /// Check if `mission.sqm` and `description.ext` could be read
+    pub const fn parsed(&self) -> bool {
+        self.template_version.is_some()
+    }
+}
@@ -0,0 +1,686 @@
+use crate::annotation::Level;
+
+#[derive(Debug)]
+/// A finding that a rule can report
+pub struct Code {
+    /// Stable code, `MR012`
+    pub code: &'static str,
+    /// Stable name, `guard-waypoint`
+    pub slug: &'static str,
+    /// Short title, `Guard waypoint`
+    pub title: &'static str,
+    /// The rule that reports the finding
+    pub rule: &'static str,
+    /// The level the finding is reported with, unless configured otherwise
+    pub level: Level,
+    /// Longer explanation of the finding and how to resolve it
+    pub explanation: &'static str,
+}
+
+impl Code {
+    #[must_use]
+    /// Title used for annotations, `MR012 Guard waypoint`
+    pub fn heading(&self) -> String {
+        format!("{} {}", self.code, self.title)
+    }
+}
+
+#[derive(Debug)]
+/// A rule that can be configured in `missionreviewer.toml`
+pub struct Rule {
+    /// Stable rule ID
+    pub id: &'static str,
+    /// `synixe_type` values the rule runs for
+    pub types: &'static [i32],
+    /// The findings the rule can report
+    pub codes: &'static [&'static Code],
+}
+
+/// All mission types, 0: Contract, 1: Sub-Contract, 2: Training, 3: Special
+const ALL_TYPES: &[i32] = &[0, 1, 2, 3];
+
+pub static RULES: [Rule; 14] = [
+    Rule {
+        id: "mission",
+        types: ALL_TYPES,
+        codes: &[
+            &MISSION_MISSING,
+            &MISSION_INVALID,
+            &MISSION_PROCESS,
+            &DESCRIPTION_MISSING,
+            &DESCRIPTION_INVALID,
+            &DESCRIPTION_PROCESS,
+            &UNKNOWN_TEMPLATE,
+            &UNKNOWN_TYPE,
+            &TYPE_NOT_ALLOWED,
+            &INVALID_PREFIX,
+            &MISSION_BINARIZED,
+        ],
+    },
+    Rule {
+        id: "suppressions",
+        types: ALL_TYPES,
+        codes: &[&UNUSED_SUPPRESSION, &UNKNOWN_SUPPRESSION],
+    },
+    Rule {
+        id: "triggers",
+        types: ALL_TYPES,
+        codes: &[
+            &GUARD_WAYPOINT,
+            &WAYPOINT_PAIR,
+            &TRIGGER_SERVER_ONLY,
+            &TRIGGER_INTERVAL,
+            &ACTIVATION_LINK,
+            &HOLD_WAYPOINT,
+        ],
+    },
+    Rule {
+        id: "cup-parking",
+        types: ALL_TYPES,
+        codes: &[&CUP_PARKING],
+    },
+    Rule {
+        id: "zeus-module",
+        types: ALL_TYPES,
+        codes: &[&ZEUS_MODULE],
+    },
+    Rule {
+        id: "cover-map",
+        types: ALL_TYPES,
+        codes: &[
+            &COVER_MAP_SHAPE,
+            &COVER_MAP_SIZE,
+            &COVER_MAP_COUNT,
+            &COVER_MAP_OUTSIDE,
+        ],
+    },
+    Rule {
+        id: "hostiles",
+        types: ALL_TYPES,
+        codes: &[&NO_HOSTILES, &HOSTILES_NEAR_PLAYERS],
+    },
+    Rule {
+        id: "players",
+        types: ALL_TYPES,
+        codes: &[
+            &PLAYER_SIDE,
+            &PLAYER_DESCRIPTION,
+            &PLAYER_CLASS,
+            &PLAYER_COUNT,
+        ],
+    },
+    Rule {
+        id: "spawners",
+        types: ALL_TYPES,
+        codes: &[
+            &MISSING_SPAWNER,
+            &UNEXPECTED_SPAWNER,
+            &SPAWNER_OVERLAP,
+            &SPAWNER_OBSTRUCTED,
+        ],
+    },
+    Rule {
+        id: "shops",
+        types: &[0, 1, 2],
+        codes: &[
+            &SIMPLE_OBJECT_SHOP,
+            &SHOP_COUNT,
+            &SHOP_SIMULATION,
+            &SHOP_HIDDEN,
+            &SHOP_DISTANCE,
+        ],
+    },
+    Rule {
+        id: "spectator",
+        types: &[0, 1],
+        codes: &[&SPECTATOR_SCREEN],
+    },
+    Rule {
+        id: "time",
+        types: ALL_TYPES,
+        codes: &[&START_TIME, &MISSION_TIME, &EDITOR_HOUR, &EDITOR_MINUTE],
+    },
+    Rule {
+        id: "briefing",
+        types: ALL_TYPES,
+        codes: &[
+            &BRIEFING_MISSING,
+            &BRIEFING_NOT_HTML,
+            &BRIEFING_UNEDITED,
+            &BRIEFING_LENGTH,
+            &BRIEFING_AMPERSAND,
+        ],
+    },
+    Rule {
+        id: "name-summary-author",
+        types: ALL_TYPES,
+        codes: &[
+            &ON_LOAD_NAME,
+            &ON_LOAD_MISSION,
+            &ON_LOAD_MISSION_STYLE,
+            &AUTHOR,
+        ],
+    },
+];
+
+/// Find a rule by its ID
+pub fn rule(id: &str) -> Option<&'static Rule> {
+    RULES.iter().find(|rule| rule.id == id)
+}
+
+/// Find a code by its code or slug
+pub fn code(id: &str) -> Option<&'static Code> {
+    RULES
+        .iter()
+        .flat_map(|rule| rule.codes.iter().copied())
+        .find(|code| code.code == id || code.slug == id)
+}
+
+/// Check if an ID refers to a rule, a code or a slug
+pub fn is_known(id: &str) -> bool {
+    rule(id).is_some() || code(id).is_some()
+}
+
+/// Name of a `synixe_type`
+pub const fn type_name(synixe_type: i32) -> &'static str {
+    match synixe_type {
+        0 => "Contract",
+        1 => "Sub-Contract",
+        2 => "Training",
+        3 => "Special",
+        _ => "Unknown",
+    }
+}
+
+pub static MISSION_MISSING: Code = Code {
+    code: "MR001",
+    slug: "mission-missing",
+    title: "mission.sqm missing",
+    rule: "mission",
+    level: Level::Error,
+    explanation: "The mission folder has no `mission.sqm`. Save the mission from the Eden editor into this folder.",
+};
+
+pub static MISSION_INVALID: Code = Code {
+    code: "MR002",
+    slug: "mission-invalid",
+    title: "mission.sqm unreadable",
+    rule: "mission",
+    level: Level::Error,
+    explanation: "`mission.sqm` could not be read as text or decoded as a binarized config.",
+};
+
+pub static MISSION_PROCESS: Code = Code {
+    code: "MR003",
+    slug: "mission-process",
+    title: "mission.sqm invalid",
+    rule: "mission",
+    level: Level::Error,
+    explanation: "`mission.sqm` could not be preprocessed or parsed. It was likely edited by hand, save it again from the Eden editor.",
+};
+
+pub static DESCRIPTION_MISSING: Code = Code {
+    code: "MR004",
+    slug: "description-missing",
+    title: "description.ext missing",
+    rule: "mission",
+    level: Level::Error,
+    explanation:
+        "The mission folder has no `description.ext`. Start the mission from the latest template.",
+};
+
+pub static DESCRIPTION_INVALID: Code = Code {
+    code: "MR005",
+    slug: "description-invalid",
+    title: "description.ext unreadable",
+    rule: "mission",
+    level: Level::Error,
+    explanation: "`description.ext` could not be read as text, make sure it is saved as UTF-8.",
+};
+
+pub static DESCRIPTION_PROCESS: Code = Code {
+    code: "MR006",
+    slug: "description-process",
+    title: "description.ext invalid",
+    rule: "mission",
+    level: Level::Error,
+    explanation: "`description.ext` or `edit_me/description.ext` could not be preprocessed or parsed. Check the reported error for a missing semicolon, quote or include.",
+};
+
+pub static UNKNOWN_TEMPLATE: Code = Code {
+    code: "MR007",
+    slug: "unknown-template",
+    title: "Unknown template",
+    rule: "mission",
+    level: Level::Error,
+    explanation: "`synixe_template` in `do_not_edit/description.ext` is not a known template version. Do not edit files in `do_not_edit`, update them from the latest template instead.",
+};
+
+pub static UNKNOWN_TYPE: Code = Code {
+    code: "MR008",
+    slug: "unknown-type",
+    title: "Unknown mission type",
+    rule: "mission",
+    level: Level::Error,
+    explanation: "`synixe_type` in `edit_me/description.ext` must be 0 (Contract), 1 (Sub-Contract), 2 (Training) or 3 (Special).",
+};
+
+pub static TYPE_NOT_ALLOWED: Code = Code {
+    code: "MR009",
+    slug: "type-not-allowed",
+    title: "Mission type not allowed",
+    rule: "mission",
+    level: Level::Error,
+    explanation: "The `synixe_type` of the mission is not one of the types allowed in its folder by `missionreviewer.toml`. Move the mission or change its type.",
+};
+
+pub static INVALID_PREFIX: Code = Code {
+    code: "MR010",
+    slug: "invalid-prefix",
+    title: "Invalid prefix",
+    rule: "mission",
+    level: Level::Error,
+    explanation: "The mission folder name does not start with one of the prefixes allowed in its folder by `missionreviewer.toml`. Only checked when the repository has a `missionreviewer.toml`.",
+};
+
+pub static UNUSED_SUPPRESSION: Code = Code {
+    code: "MR011",
+    slug: "unused-suppression",
+    title: "Unused suppression",
+    rule: "suppressions",
+    level: Level::Notice,
+    explanation: "A `// missionreviewer: allow` comment did not silence any finding. Remove it so it does not hide a future problem.",
+};
+
+pub static GUARD_WAYPOINT: Code = Code {
+    code: "MR012",
+    slug: "guard-waypoint",
+    title: "Guard waypoint",
+    rule: "triggers",
+    level: Level::Error,
+    explanation: "Guard waypoints make groups react to anything in the whole mission and are expensive on the server. Use a Seek and Destroy or Move waypoint instead.",
+};
+
+pub static WAYPOINT_PAIR: Code = Code {
+    code: "MR013",
+    slug: "waypoint-pair",
+    title: "Waypoint activation pair",
+    rule: "triggers",
+    level: Level::Error,
+    explanation: "Waypoints can only activate each other as Transport Unload with Get Out, or Get In with Load. Other pairs do not synchronize reliably.",
+};
+
+pub static TRIGGER_SERVER_ONLY: Code = Code {
+    code: "MR014",
+    slug: "trigger-server-only",
+    title: "Trigger not server only",
+    rule: "triggers",
+    level: Level::Error,
+    explanation: "Triggers that activate waypoints must be set to \"Server Only\", otherwise they are evaluated on every client.",
+};
+
+pub static TRIGGER_INTERVAL: Code = Code {
+    code: "MR015",
+    slug: "trigger-interval",
+    title: "Trigger interval too low",
+    rule: "triggers",
+    level: Level::Error,
+    explanation: "Triggers that activate waypoints must have an interval of at least 0.6 seconds, lower intervals waste server performance.",
+};
+
+pub static ACTIVATION_LINK: Code = Code {
+    code: "MR016",
+    slug: "activation-link",
+    title: "Invalid waypoint activation",
+    rule: "triggers",
+    level: Level::Error,
+    explanation: "A Waypoint Activation link must connect a trigger to a waypoint. Remove the link and synchronize the trigger to the waypoint again.",
+};
+
+pub static HOLD_WAYPOINT: Code = Code {
+    code: "MR017",
+    slug: "hold-waypoint",
+    title: "Hold waypoint activation",
+    rule: "triggers",
+    level: Level::Error,
+    explanation: "A Hold waypoint never completes on its own, the trigger linked to it must be of type Skip Waypoint.",
+};
+
+pub static CUP_PARKING: Code = Code {
+    code: "MR018",
+    slug: "cup-parking-sign",
+    title: "CUP parking sign",
+    rule: "cup-parking",
+    level: Level::Error,
+    explanation: "The CUP parking signs float above the ground. Use the vanilla \"Parking Lot\" (RoadSign_Livonia_parking) sign instead.",
+};
+
+pub static ZEUS_MODULE: Code = Code {
+    code: "MR019",
+    slug: "zeus-module-placed",
+    title: "Zeus module",
+    rule: "zeus-module",
+    level: Level::Error,
+    explanation: "Zeus is provided by the server, placed Zeus modules conflict with it. Use ACE interact for local testing.",
+};
+
+pub static COVER_MAP_SHAPE: Code = Code {
+    code: "MR020",
+    slug: "cover-map-shape",
+    title: "Cover map shape",
+    rule: "cover-map",
+    level: Level::Error,
+    explanation: "Cover maps must be rectangles, set areaIsRectangle=1 on the module.",
+};
+
+pub static COVER_MAP_SIZE: Code = Code {
+    code: "MR021",
+    slug: "cover-map-size",
+    title: "Cover map size",
+    rule: "cover-map",
+    level: Level::Error,
+    explanation: "The width and length of a cover map must be multiples of 100 metres so the grid lines up with the map.",
+};
+
+pub static NO_HOSTILES: Code = Code {
+    code: "MR022",
+    slug: "no-hostiles",
+    title: "No hostiles",
+    rule: "hostiles",
+    level: Level::Error,
+    explanation:
+        "The mission has no East units, and no Independent units that are hostile to West.",
+};
+
+pub static PLAYER_SIDE: Code = Code {
+    code: "MR023",
+    slug: "player-side",
+    title: "Player side",
+    rule: "players",
+    level: Level::Error,
+    explanation: "Contractors are always on the West side.",
+};
+
+pub static PLAYER_DESCRIPTION: Code = Code {
+    code: "MR024",
+    slug: "player-description",
+    title: "Player description",
+    rule: "players",
+    level: Level::Error,
+    explanation: "Every playable unit must have the role description 'Contractor'.",
+};
+
+pub static PLAYER_CLASS: Code = Code {
+    code: "MR025",
+    slug: "player-class",
+    title: "Player class",
+    rule: "players",
+    level: Level::Error,
+    explanation: "Every playable unit must be a synixe_factions_synixe_Contractor, loadouts are provided by the persistent gear system.",
+};
+
+pub static PLAYER_COUNT: Code = Code {
+    code: "MR026",
+    slug: "player-count",
+    title: "Player count",
+    rule: "players",
+    level: Level::Error,
+    explanation: "The number of playable units must match the number in the mission prefix, CO30 requires 30 playable units.",
+};
+
+pub static MISSING_SPAWNER: Code = Code {
+    code: "MR027",
+    slug: "missing-spawner",
+    title: "Missing spawner",
+    rule: "spawners",
+    level: Level::Error,
+    explanation: "Contracts need a land and a thing spawner. Set synixe_no_vehicles = 1 when the mission intentionally has no vehicles.",
+};
+
+pub static UNEXPECTED_SPAWNER: Code = Code {
+    code: "MR028",
+    slug: "unexpected-spawner",
+    title: "Spawner not allowed",
+    rule: "spawners",
+    level: Level::Error,
+    explanation: "Only contracts and sub-contracts can have spawners.",
+};
+
+pub static SIMPLE_OBJECT_SHOP: Code = Code {
+    code: "MR029",
+    slug: "simple-object-shop",
+    title: "Shop is a simple object",
+    rule: "shops",
+    level: Level::Error,
+    explanation: "Shops are interacted with and can not be simple objects, untick \"Simple Object\" on the shop.",
+};
+
+pub static SHOP_COUNT: Code = Code {
+    code: "MR030",
+    slug: "shop-count",
+    title: "Shop count",
+    rule: "shops",
+    level: Level::Error,
+    explanation: "Missions with a persistent loadout need enough shops near the player start. The number of shops is `required-shops` in the `[thresholds]` of `missionreviewer.toml`.",
+};
+
+pub static SPECTATOR_SCREEN: Code = Code {
+    code: "MR031",
+    slug: "spectator-screen",
+    title: "Spectator screen missing",
+    rule: "spectator",
+    level: Level::Error,
+    explanation:
+        "Contracts and sub-contracts need a synixe_spectator_screen near the player start.",
+};
+
+pub static START_TIME: Code = Code {
+    code: "MR032",
+    slug: "start-time",
+    title: "Start time",
+    rule: "time",
+    level: Level::Error,
+    explanation:
+        "synixe_start_time in `edit_me/description.ext` must be set to an hour between 0 and 24.",
+};
+
+pub static MISSION_TIME: Code = Code {
+    code: "MR033",
+    slug: "mission-time",
+    title: "Mission time missing",
+    rule: "time",
+    level: Level::Error,
+    explanation: "The editor time could not be found in `mission.sqm`, and the map has no default start time in `starts.txt`.",
+};
+
+pub static EDITOR_HOUR: Code = Code {
+    code: "MR034",
+    slug: "editor-hour",
+    title: "Editor hour",
+    rule: "time",
+    level: Level::Error,
+    explanation: "The editor time must be one hour before synixe_start_time, the hour between is used for the briefing.",
+};
+
+pub static EDITOR_MINUTE: Code = Code {
+    code: "MR035",
+    slug: "editor-minute",
+    title: "Editor minute",
+    rule: "time",
+    level: Level::Error,
+    explanation: "The editor time must be on the hour, set the minutes to 0.",
+};
+
+pub static BRIEFING_MISSING: Code = Code {
+    code: "MR036",
+    slug: "briefing-missing",
+    title: "Briefing missing",
+    rule: "briefing",
+    level: Level::Error,
+    explanation: "The `edit_me/briefing` folder is missing, copy it from the latest template.",
+};
+
+pub static BRIEFING_NOT_HTML: Code = Code {
+    code: "MR037",
+    slug: "briefing-not-html",
+    title: "Briefing not HTML",
+    rule: "briefing",
+    level: Level::Error,
+    explanation: "Only `.html` files are read from `edit_me/briefing`.",
+};
+
+pub static BRIEFING_UNEDITED: Code = Code {
+    code: "MR038",
+    slug: "briefing-unedited",
+    title: "Briefing not edited",
+    rule: "briefing",
+    level: Level::Error,
+    explanation: "The briefing still contains INSERT placeholders from the template.",
+};
+
+pub static BRIEFING_LENGTH: Code = Code {
+    code: "MR039",
+    slug: "briefing-length",
+    title: "Briefing too long",
+    rule: "briefing",
+    level: Level::Error,
+    explanation: "Briefings are posted to Discord, which limits messages to 2000 characters.",
+};
+
+pub static BRIEFING_AMPERSAND: Code = Code {
+    code: "MR040",
+    slug: "briefing-ampersand",
+    title: "Unescaped ampersand",
+    rule: "briefing",
+    level: Level::Error,
+    explanation: "A bare & breaks the briefing HTML in game, write &amp; instead.",
+};
+
+pub static ON_LOAD_NAME: Code = Code {
+    code: "MR041",
+    slug: "on-load-name",
+    title: "OnLoadName",
+    rule: "name-summary-author",
+    level: Level::Error,
+    explanation: "OnLoadName in `edit_me/description.ext` must be set to the name of the mission.",
+};
+
+pub static ON_LOAD_MISSION: Code = Code {
+    code: "MR042",
+    slug: "on-load-mission",
+    title: "OnLoadMission",
+    rule: "name-summary-author",
+    level: Level::Error,
+    explanation:
+        "OnLoadMission in `edit_me/description.ext` must be set to a summary of the mission.",
+};
+
+pub static ON_LOAD_MISSION_STYLE: Code = Code {
+    code: "MR043",
+    slug: "on-load-mission-style",
+    title: "OnLoadMission style",
+    rule: "name-summary-author",
+    level: Level::Warning,
+    explanation: "OnLoadMission should be a single sentence without a trailing period.",
+};
+
+pub static AUTHOR: Code = Code {
+    code: "MR044",
+    slug: "author",
+    title: "Author",
+    rule: "name-summary-author",
+    level: Level::Error,
+    explanation:
+        "author in `edit_me/description.ext` must be set to the name of the mission maker.",
+};
+
+pub static MISSION_BINARIZED: Code = Code {
+    code: "MR045",
+    slug: "mission-binarized",
+    title: "mission.sqm binarized",
+    rule: "mission",
+    level: Level::Warning,
+    explanation: "`mission.sqm` is binarized, so changes to it can not be reviewed in a diff. Untick \"Binarize the Scenario File\" in the Eden attributes and save again, which writes `binarizationWanted=0`.",
+};
+
+pub static SPAWNER_OVERLAP: Code = Code {
+    code: "MR046",
+    slug: "spawner-overlap",
+    title: "Spawners overlap",
+    rule: "spawners",
+    level: Level::Error,
+    explanation: "The areas of two spawners overlap, so vehicles spawned on one can collide with vehicles on the other. Move the spawners apart or use smaller ones.",
+};
+
+pub static SPAWNER_OBSTRUCTED: Code = Code {
+    code: "MR047",
+    slug: "spawner-obstructed",
+    title: "Spawner obstructed",
+    rule: "spawners",
+    level: Level::Error,
+    explanation: "An object is placed in the area of a spawner, so spawned vehicles can collide with it. Move the object or the spawner, objects hidden with \"Show Model\" unticked are ignored.",
+};
+
+pub static HOSTILES_NEAR_PLAYERS: Code = Code {
+    code: "MR048",
+    slug: "hostiles-near-players",
+    title: "Hostiles near players",
+    rule: "hostiles",
+    level: Level::Warning,
+    explanation: "A hostile group leader or crewed vehicle is placed close to the playable units and can engage them as the mission loads. Move it further away, the distance is `hostile-distance` in the `[thresholds]` of `missionreviewer.toml`.",
+};
+
+pub static COVER_MAP_COUNT: Code = Code {
+    code: "MR049",
+    slug: "cover-map-count",
+    title: "Cover map count",
+    rule: "cover-map",
+    level: Level::Warning,
+    explanation: "A mission should have one cover map, contracts and sub-contracts need one over the area of operations.",
+};
+
+pub static COVER_MAP_OUTSIDE: Code = Code {
+    code: "MR050",
+    slug: "cover-map-outside",
+    title: "Outside of the cover map",
+    rule: "cover-map",
+    level: Level::Warning,
+    explanation: "Playable units, shops, spawners and hostile groups must be inside the cover map, so the briefing map shows where the mission takes place.",
+};
+
+pub static SHOP_SIMULATION: Code = Code {
+    code: "MR051",
+    slug: "shop-simulation",
+    title: "Shop simulation disabled",
+    rule: "shops",
+    level: Level::Error,
+    explanation:
+        "Shops are interacted with and need simulation, tick \"Enable Simulation\" on the shop.",
+};
+
+pub static SHOP_HIDDEN: Code = Code {
+    code: "MR052",
+    slug: "shop-hidden",
+    title: "Shop hidden",
+    rule: "shops",
+    level: Level::Error,
+    explanation: "Hidden shops can not be found by players, tick \"Show Model\" on the shop.",
+};
+
+pub static SHOP_DISTANCE: Code = Code {
+    code: "MR053",
+    slug: "shop-distance",
+    title: "Shop far from players",
+    rule: "shops",
+    level: Level::Warning,
+    explanation: "Shops must be placed near the player start. The distance is `shop-distance` in the `[thresholds]` of `missionreviewer.toml`.",
+};
+
+pub static UNKNOWN_SUPPRESSION: Code = Code {
+    code: "MR054",
+    slug: "unknown-suppression",
+    title: "Unknown suppression",
+    rule: "suppressions",
+    level: Level::Warning,
+    explanation: "A `// missionreviewer: allow` comment names a rule that does not exist, so it silences nothing. Use a rule ID, a code like `MR011` or a slug like `unused-suppression`.",
+};
@@ -0,0 +1,456 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use globset::Glob;
+use serde::Deserialize;
+
+use crate::{
+    annotation::{Annotation, Level},
+    rules,
+};
+
+/// Name of the configuration file, read from the root of the mission repository
+pub const FILE_NAME: &str = "missionreviewer.toml";
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+/// Repository configuration
+///
+/// ```toml
+/// shared = ["starts.txt", "functions/**"]
+///
+/// [[roots]]
+/// path = "campaigns"
+/// depth = 2
+/// prefixes = ["CCO"]
+/// types = [0, 1]
+///
+/// [rules]
+/// cup-parking = "warning"
+///
+/// [thresholds]
+/// hostile-distance = 500
+/// required-shops = 2
+///
+/// [[overrides]]
+/// paths = ["specials/*"]
+/// types = [3]
+/// rules = { spawners = "off" }
+/// thresholds = { hostile-distance = 200 }
+/// ```
+pub struct Settings {
+    #[serde(default = "default_roots")]
+    /// Folders that contain missions
+    pub roots: Vec<Root>,
+    #[serde(default = "default_shared")]
+    /// Globs of files used by every mission, a change to one reviews all missions with `--changed-since`
+    pub shared: Vec<String>,
+    #[serde(default)]
+    /// Severity of each rule, code or slug, for all missions
+    pub rules: HashMap<String, Severity>,
+    #[serde(default)]
+    /// Limits used by rules, for all missions
+    pub thresholds: Thresholds,
+    #[serde(default)]
+    /// Severity of each rule, for some missions, later overrides take precedence
+    pub overrides: Vec<Override>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+/// A folder that contains missions
+pub struct Root {
+    /// The folder, relative to the repository root
+    pub path: PathBuf,
+    #[serde(default = "default_depth")]
+    /// How many folders below `path` the missions are, 1 means `path/<mission>`
+    pub depth: usize,
+    #[serde(default)]
+    /// Prefixes a mission folder name can start with, any prefix is allowed when empty
+    pub prefixes: Vec<String>,
+    #[serde(default)]
+    /// `synixe_type` values allowed in this root, any type is allowed when empty
+    pub types: Vec<i32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+/// Rule severities for missions matching the paths and types
+pub struct Override {
+    #[serde(default)]
+    /// Globs matched against the mission folder, any folder matches when empty
+    pub paths: Vec<String>,
+    #[serde(default)]
+    /// `synixe_type` values, any type matches when empty
+    pub types: Vec<i32>,
+    #[serde(default)]
+    /// Severity of each rule, code or slug
+    pub rules: HashMap<String, Severity>,
+    #[serde(default)]
+    /// Limits used by rules, only the ones that are set replace the current value
+    pub thresholds: ThresholdOverrides,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+/// Limits used by rules
+pub struct Thresholds {
+    #[serde(default = "default_hostile_distance")]
+    /// Closest a hostile group or vehicle can be to a playable unit, in meters
+    pub hostile_distance: f32,
+    #[serde(default = "default_shop_distance")]
+    /// Furthest a shop can be from a playable unit, in meters
+    pub shop_distance: f32,
+    #[serde(default = "default_required_shops")]
+    /// Fewest shops a mission with shops can have
+    pub required_shops: usize,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+/// Limits used by rules, for some missions
+pub struct ThresholdOverrides {
+    pub hostile_distance: Option<f32>,
+    pub shop_distance: Option<f32>,
+    pub required_shops: Option<usize>,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Self {
+            hostile_distance: default_hostile_distance(),
+            shop_distance: default_shop_distance(),
+            required_shops: default_required_shops(),
+        }
+    }
+}
+
+impl Thresholds {
+    /// Replace the limits that are set in an override
+    fn apply(&mut self, overrides: &ThresholdOverrides) {
+        if let Some(hostile_distance) = overrides.hostile_distance {
+            self.hostile_distance = hostile_distance;
+        }
+        if let Some(shop_distance) = overrides.shop_distance {
+            self.shop_distance = shop_distance;
+        }
+        if let Some(required_shops) = overrides.required_shops {
+            self.required_shops = required_shops;
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+/// Severity a rule is reported with
+pub enum Severity {
+    /// Do not run the rule
+    Off,
+    /// Report as a notice
+    Notice,
+    /// Report as a warning
+    Warning,
+    /// Report as an error
+    Error,
+}
+
+impl Severity {
+    /// The annotation level, `None` when the rule is off
+    pub const fn level(self) -> Option<Level> {
+        match self {
+            Self::Off => None,
+            Self::Notice => Some(Level::Notice),
+            Self::Warning => Some(Level::Warning),
+            Self::Error => Some(Level::Error),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+/// Rule severities resolved for a single mission
+///
+/// One table per source, `[rules]` first and then each matching override in order
+pub struct Severities(Vec<HashMap<String, Severity>>);
+
+impl Severities {
+    /// Check if a rule should run
+    pub fn enabled(&self, rule: &str) -> bool {
+        self.0.iter().rev().find_map(|rules| rules.get(rule)) != Some(&Severity::Off)
+    }
+
+    /// The severity of an annotation, from the last source that sets its code, slug or rule
+    ///
+    /// Within a source, a severity set for a code or slug takes precedence over one set for
+    /// its rule
+    fn severity(&self, annotation: &Annotation) -> Option<Severity> {
+        let slug = rules::code(&annotation.code).map(|code| code.slug);
+        self.0.iter().rev().find_map(|rules| {
+            rules
+                .get(&annotation.code)
+                .or_else(|| slug.and_then(|slug| rules.get(slug)))
+                .or_else(|| rules.get(&annotation.rule))
+                .copied()
+        })
+    }
+
+    /// Drop annotations of disabled rules and remap the level of the others
+    pub fn apply(&self, messages: Vec<Annotation>) -> Vec<Annotation> {
+        messages
+            .into_iter()
+            .filter_map(|mut annotation| match self.severity(&annotation) {
+                Some(severity) => {
+                    annotation.level = severity.level()?;
+                    Some(annotation)
+                }
+                None => Some(annotation),
+            })
+            .collect()
+    }
+}
+
+impl Default for Settings {
+    /// Settings without a `missionreviewer.toml`
+    ///
+    /// Prefixes were not enforced before the file existed, so the check is off until a file
+    /// enables it
+    fn default() -> Self {
+        Self {
+            roots: default_roots(),
+            shared: default_shared(),
+            rules: HashMap::from([(rules::INVALID_PREFIX.slug.to_string(), Severity::Off)]),
+            thresholds: Thresholds::default(),
+            overrides: Vec::new(),
+        }
+    }
+}
+
+impl FromStr for Settings {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let settings: Self =
+            toml::from_str(s).map_err(|e| format!("`{}` is invalid: {}", FILE_NAME, e))?;
+        for rule in settings
+            .rules
+            .keys()
+            .chain(settings.overrides.iter().flat_map(|o| o.rules.keys()))
+        {
+            if !rules::is_known(rule) {
+                return Err(format!("`{}` has an unknown rule `{}`", FILE_NAME, rule));
+            }
+        }
+        for path in settings
+            .shared
+            .iter()
+            .chain(settings.overrides.iter().flat_map(|o| &o.paths))
+        {
+            if let Err(e) = Glob::new(path) {
+                return Err(format!("`{}` has an invalid glob: {}", FILE_NAME, e));
+            }
+        }
+        Ok(settings)
+    }
+}
+
+impl Settings {
+    /// Read the configuration from `dir`, falling back to the defaults when it is absent
+    pub fn load(dir: &Path) -> Result<Self, String> {
+        let path = dir.join(FILE_NAME);
+        if !path.is_file() {
+            return Ok(Self::default());
+        }
+        std::fs::read_to_string(&path)
+            .map_err(|e| format!("failed to read {}: {}", path.display(), e))?
+            .parse()
+    }
+
+    /// Find all missions in the configured roots
+    ///
+    /// When `filters` is not empty, only missions whose path contains one of them are returned
+    pub fn missions(&self, filters: &[String]) -> Vec<PathBuf> {
+        let mut missions = Vec::new();
+        for root in &self.roots {
+            if !root.path.exists() {
+                continue;
+            }
+            for entry in walkdir::WalkDir::new(&root.path)
+                .min_depth(root.depth)
+                .max_depth(root.depth)
+                .sort_by_file_name()
+                .into_iter()
+                .filter_map(Result::ok)
+            {
+                if !entry.file_type().is_dir() {
+                    continue;
+                }
+                if filters.is_empty()
+                    || filters
+                        .iter()
+                        .any(|filter| entry.path().display().to_string().contains(filter))
+                {
+                    missions.push(entry.into_path());
+                }
+            }
+        }
+        missions
+    }
+
+    /// Resolve the rule severities for a mission
+    ///
+    /// Without a type, only the overrides that match any type are used
+    pub fn severities(&self, mission: &Path, synixe_type: Option<i32>) -> Severities {
+        let mut severities = vec![self.rules.clone()];
+        severities.extend(
+            self.overrides
+                .iter()
+                .filter(|o| o.applies(mission, synixe_type))
+                .map(|o| o.rules.clone()),
+        );
+        Severities(severities)
+    }
+
+    /// Resolve the thresholds for a mission
+    pub fn thresholds(&self, mission: &Path, synixe_type: Option<i32>) -> Thresholds {
+        let mut thresholds = self.thresholds;
+        for o in self
+            .overrides
+            .iter()
+            .filter(|o| o.applies(mission, synixe_type))
+        {
+            thresholds.apply(&o.thresholds);
+        }
+        thresholds
+    }
+
+    /// Check if a file, relative to the repository root, is used by every mission
+    pub fn is_shared(&self, path: &Path) -> bool {
+        let path = normalize(path);
+        self.shared.iter().any(|shared| {
+            Glob::new(shared)
+                .map(|glob| glob.compile_matcher().is_match(&path))
+                .unwrap_or_default()
+        })
+    }
+
+    /// Find the root a mission belongs to
+    pub fn root_for(&self, mission: &Path) -> Option<&Root> {
+        self.roots.iter().find(|root| {
+            mission
+                .strip_prefix(&root.path)
+                .map(|rest| rest.components().count() == root.depth)
+                .unwrap_or_default()
+        })
+    }
+}
+
+impl Root {
+    /// Check that the mission folder name starts with an allowed prefix
+    pub fn check_prefix(&self, dir: &Path) -> Vec<Annotation> {
+        let mut messages = vec![];
+        if self.prefixes.is_empty() {
+            return messages;
+        }
+        let name = dir
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default();
+        if !self
+            .prefixes
+            .iter()
+            .any(|prefix| name.starts_with(prefix.as_str()))
+        {
+            messages.push(Annotation::new(
+                None,
+                dir.display().to_string(),
+                0..0,
+                format!(
+                    "Invalid prefix for {}, expected one of {}",
+                    dir.display(),
+                    self.prefixes.join(", ")
+                ),
+                &rules::INVALID_PREFIX,
+            ));
+        }
+        messages
+    }
+
+    /// Check if a `synixe_type` is allowed in this root
+    pub fn allows_type(&self, synixe_type: i32) -> bool {
+        self.types.is_empty() || self.types.contains(&synixe_type)
+    }
+}
+
+impl Override {
+    /// Check if the override applies to a mission, `None` when its type could not be read
+    pub fn applies(&self, mission: &Path, synixe_type: Option<i32>) -> bool {
+        if !self.types.is_empty()
+            && !synixe_type.is_some_and(|synixe_type| self.types.contains(&synixe_type))
+        {
+            return false;
+        }
+        if self.paths.is_empty() {
+            return true;
+        }
+        let mission = normalize(mission);
+        self.paths.iter().any(|path| {
+            Glob::new(path)
+                .map(|glob| glob.compile_matcher().is_match(&mission))
+                .unwrap_or_default()
+        })
+    }
+}
+
+/// Forward slashed path without a leading `./`, for matching globs
+fn normalize(path: &Path) -> String {
+    path.display()
+        .to_string()
+        .replace('\\', "/")
+        .trim_start_matches("./")
+        .to_string()
+}
+
+fn default_shared() -> Vec<String> {
+    vec!["starts.txt".to_string(), FILE_NAME.to_string()]
+}
+
+fn default_roots() -> Vec<Root> {
+    vec![
+        Root {
+            path: PathBuf::from("contracts"),
+            depth: 1,
+            prefixes: vec!["CO".to_string(), "SCO".to_string(), "TRA".to_string()],
+            types: Vec::new(),
+        },
+        Root {
+            path: PathBuf::from("specials"),
+            depth: 1,
+            prefixes: vec!["CO".to_string(), "SCO".to_string(), "TRA".to_string()],
+            types: Vec::new(),
+        },
+        Root {
+            path: PathBuf::from("theatres"),
+            depth: 2,
+            prefixes: vec!["CCO".to_string(), "TCO".to_string(), "TT".to_string()],
+            types: Vec::new(),
+        },
+    ]
+}
+
+const fn default_depth() -> usize {
+    1
+}
+
+const fn default_hostile_distance() -> f32 {
+    500.0
+}
+
+const fn default_shop_distance() -> f32 {
+    100.0
+}
+
+const fn default_required_shops() -> usize {
+    2
+}
@@ -0,0 +1,122 @@
+use std::{ops::Range, path::Path};
+
+use crate::{annotation::Annotation, rules};
+
+/// Marker that starts a suppression comment
+///
+/// `// missionreviewer: allow guard-waypoint, spawners`
+pub const MARKER: &str = "missionreviewer:";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A rule silenced by a suppression comment
+pub struct Suppression {
+    /// The rule, code or slug that is silenced
+    pub rule: String,
+    /// The file containing the comment
+    pub path: String,
+    /// The span of the comment
+    pub range: Range<usize>,
+}
+
+impl Suppression {
+    /// Check if the suppression silences an annotation
+    pub fn matches(&self, annotation: &Annotation) -> bool {
+        annotation.matches(&self.rule)
+    }
+}
+
+/// Read the suppression comments of a mission
+///
+/// Comments are read from `edit_me/description.ext` and `mission.sqm`
+pub fn read(dir: &Path) -> Vec<Suppression> {
+    [
+        dir.join("edit_me").join("description.ext"),
+        dir.join("mission.sqm"),
+    ]
+    .iter()
+    .filter_map(|path| {
+        std::fs::read_to_string(path)
+            .ok()
+            .map(|content| parse(&path.display().to_string(), &content))
+    })
+    .flatten()
+    .collect()
+}
+
+/// Find the suppression comments in the content of a file
+pub fn parse(path: &str, content: &str) -> Vec<Suppression> {
+    let mut suppressions = vec![];
+    let mut offset = 0;
+    for line in content.split_inclusive('\n') {
+        if let Some(start) = line.find("//") {
+            let comment = line[start + 2..].trim_start();
+            if let Some(rules) = comment
+                .strip_prefix(MARKER)
+                .map(str::trim_start)
+                .and_then(|rest| rest.strip_prefix("allow"))
+                .filter(|rules| rules.starts_with(char::is_whitespace))
+            {
+                let range = offset + start..offset + line.trim_end().len();
+                suppressions.extend(
+                    rules
+                        .split(|c: char| c == ',' || c.is_whitespace())
+                        .filter(|rule| !rule.is_empty())
+                        .map(|rule| Suppression {
+                            rule: rule.to_string(),
+                            path: path.to_string(),
+                            range: range.clone(),
+                        }),
+                );
+            }
+        }
+        offset += line.len();
+    }
+    suppressions
+}
+
+/// Drop the annotations silenced by the suppressions
+///
+/// Suppressions of unknown rules are reported as warnings, other suppressions that did not
+/// silence anything as notices
+pub fn apply(suppressions: &[Suppression], messages: Vec<Annotation>) -> Vec<Annotation> {
+    let mut used = vec![false; suppressions.len()];
+    let mut messages = messages
+        .into_iter()
+        .filter(|annotation| {
+            let mut keep = true;
+            for (i, suppression) in suppressions.iter().enumerate() {
+                if suppression.matches(annotation) {
+                    used[i] = true;
+                    keep = false;
+                }
+            }
+            keep
+        })
+        .collect::<Vec<_>>();
+    for (suppression, used) in suppressions.iter().zip(used) {
+        if !rules::is_known(&suppression.rule) {
+            messages.push(Annotation::new(
+                None,
+                suppression.path.clone(),
+                suppression.range.clone(),
+                format!(
+                    "Suppression of `{}` does not match any rule, code or slug",
+                    suppression.rule
+                ),
+                &rules::UNKNOWN_SUPPRESSION,
+            ));
+        } else if !used {
+            messages.push(Annotation::new(
+                None,
+                suppression.path.clone(),
+                suppression.range.clone(),
+                format!(
+                    "Suppression of `{}` is unused and can be removed",
+                    suppression.rule
+                ),
+                &rules::UNUSED_SUPPRESSION,
+            ));
+        }
+    }
+    messages
+}
@@ -12,12 +12,15 @@ pub fn check(
     dir: &Path,
     mission: (&Processed, &Config),
     config: (&Processed, &Config),
+    checks: &mut Vec<String>,
 ) -> Result<Vec<Annotation>, String> {
     let mut messages = vec![];
     // These files in templates should be untouched
     if !dir.file_name().unwrap().to_str().unwrap().starts_with("TT") {
+        checks.push("name-summary-author".to_string());
         messages.append(&mut name_summary_author(dir, config));
     }
+    checks.push("time".to_string());
     messages.append(&mut time(dir, mission, config));
     Ok(messages)
 }
@@ -4,21 +4,27 @@ use hemtt_config::Config;
 use hemtt_workspace::reporting::Processed;
 
 use crate::{
-    annotation::{Annotation, Level},
+    annotation::Annotation,
     checks::{description::name_summary_author, time::time},
+    fix::Fix,
+    rules,
 };
 
 pub fn check(
     dir: &Path,
     mission: (&Processed, &Config),
     config: (&Processed, &Config),
+    checks: &mut Vec<String>,
 ) -> Result<Vec<Annotation>, String> {
     let mut messages = vec![];
     // These files in templates should be untouched
     if !dir.file_name().unwrap().to_str().unwrap().starts_with("TT") {
+        checks.push("name-summary-author".to_string());
         messages.append(&mut name_summary_author(dir, config));
+        checks.push("briefing".to_string());
         messages.append(&mut briefing(dir));
     }
+    checks.push("time".to_string());
     messages.append(&mut time(dir, mission, config));
     Ok(messages)
 }
@@ -32,7 +38,7 @@ fn briefing(dir: &Path) -> Vec<Annotation> {
             briefing_path.display().to_string(),
             0..0,
             "Briefing folder is missing".to_string(),
-            Level::Error,
+            &rules::BRIEFING_MISSING,
         ));
         return messages;
     };
@@ -49,7 +55,7 @@ fn briefing(dir: &Path) -> Vec<Annotation> {
                     path.display().to_string(),
                     0..0,
                     "Briefing file is not an HTML file".to_string(),
-                    Level::Error,
+                    &rules::BRIEFING_NOT_HTML,
                 ));
                 continue;
             }
@@ -60,7 +66,7 @@ fn briefing(dir: &Path) -> Vec<Annotation> {
                     path.display().to_string(),
                     0..0,
                     "Briefing file is not edited".to_string(),
-                    Level::Error,
+                    &rules::BRIEFING_UNEDITED,
                 ));
             }
         }
@@ -72,20 +78,26 @@ fn briefing(dir: &Path) -> Vec<Annotation> {
                     path.display().to_string(),
                     0..0,
                     format!("Briefing {} is too long for Discord", path.display()),
-                    Level::Error,
+                    &rules::BRIEFING_LENGTH,
                 ));
             }
 
             let mut remaining_content = content.as_str();
             let mut offset = 0;
             while let Some(pos) = remaining_content.find(" & ") {
-                messages.push(Annotation::new(
-                    None,
-                    path.display().to_string(),
-                    (offset + pos + 1)..(offset + pos + 2),
-                    "Briefing file contains an unescaped ampersand. Use &amp; instead.".to_string(),
-                    Level::Error,
-                ));
+                let range = (offset + pos + 1)..(offset + pos + 2);
+                let fix = Fix::replace(None, &range, "&amp;");
+                messages.push(
+                    Annotation::new(
+                        None,
+                        path.display().to_string(),
+                        range,
+                        "Briefing file contains an unescaped ampersand. Use &amp; instead."
+                            .to_string(),
+                        &rules::BRIEFING_AMPERSAND,
+                    )
+                    .with_fix(fix),
+                );
                 offset += pos + 3;
                 remaining_content = &remaining_content[pos + 3..];
             }
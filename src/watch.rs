@@ -0,0 +1,64 @@
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use crate::{mission, output::pretty, report::Report, settings::Settings};
+
+/// How often the mission folder is checked for changes
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Review a mission every time a file in it is saved, redrawing the findings in the terminal
+///
+/// The folder is polled, a review starts once the files stop changing so a save from the
+/// editor is not reviewed halfway through
+pub fn run(dir: &PathBuf, settings: &Settings, color: bool) -> ! {
+    let mut last = None;
+    loop {
+        let mut current = snapshot(dir);
+        if last.as_ref() != Some(&current) {
+            loop {
+                std::thread::sleep(POLL_INTERVAL);
+                let next = snapshot(dir);
+                if next == current {
+                    break;
+                }
+                current = next;
+            }
+            let content = match mission::check(dir, settings) {
+                Ok(report) => pretty::render(&Report::new(vec![report]), color),
+                Err(e) => format!("{}\n", e),
+            };
+            let mut stdout = std::io::stdout().lock();
+            write!(
+                stdout,
+                "\x1b[2J\x1b[H{}\nWatching {} for changes, press Ctrl+C to stop\n",
+                content,
+                dir.display()
+            )
+            .and_then(|()| stdout.flush())
+            .expect("write to stdout");
+            last = Some(current);
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Path, modification time and size of every file in a folder
+fn snapshot(dir: &Path) -> Vec<(PathBuf, Option<SystemTime>, u64)> {
+    walkdir::WalkDir::new(dir)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| {
+            let metadata = entry.metadata().ok();
+            (
+                entry.into_path(),
+                metadata.as_ref().and_then(|m| m.modified().ok()),
+                metadata.map(|m| m.len()).unwrap_or_default(),
+            )
+        })
+        .collect()
+}
@@ -0,0 +1,86 @@
+mod common;
+
+use std::path::Path;
+
+use missionreviewer::{
+    annotation::Annotation,
+    baseline::{Baseline, Fingerprint},
+    report::{MissionReport, Report},
+    rules,
+};
+
+#[test]
+fn fingerprint() {
+    let mission = MissionReport::new(Path::new("./contracts/CO30_Brett_Harmonics.pja308"));
+    assert_eq!(
+        Fingerprint::new(&mission, &common::guard_waypoint(0..0), None),
+        Fingerprint {
+            mission: "contracts/CO30_Brett_Harmonics.pja308".to_string(),
+            code: "MR012".to_string(),
+            file: "mission.sqm".to_string(),
+            subject: String::new(),
+        }
+    );
+}
+
+#[test]
+fn hides_known_findings() {
+    let mut mission = MissionReport::new(Path::new("contracts/CO30_Brett_Harmonics.pja308"));
+    mission.annotations.push(common::guard_waypoint(0..0));
+    let baseline = Baseline::new(&Report::new(vec![mission.clone()]));
+
+    // The span moved and a second guard waypoint was added
+    mission.annotations = vec![
+        common::guard_waypoint(10..20),
+        common::guard_waypoint(30..40),
+    ];
+    let mut report = Report::new(vec![mission]);
+    assert_eq!(baseline.apply(&mut report), 1);
+    assert_eq!(report.annotations().count(), 1);
+}
+
+#[test]
+fn ignores_message_and_line() {
+    let dir = std::env::temp_dir().join(format!("missionreviewer-baseline-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("mission.sqm");
+    let finding = |content: &str, class: &str, message: &str| {
+        std::fs::write(&path, content).unwrap();
+        let start = content.find(class).unwrap();
+        Annotation::new(
+            None,
+            path.display().to_string(),
+            start..start + class.len(),
+            message.to_string(),
+            &rules::SPAWNER_OBSTRUCTED,
+        )
+    };
+    let mut mission = MissionReport::new(&dir);
+    mission.annotations.push(finding(
+        "type=\"Land_Cargo20_red_F\";\n",
+        "Land_Cargo20_red_F",
+        "Spawner is obstructed by a container 1.20m away",
+    ));
+    let baseline = Baseline::new(&Report::new(vec![mission.clone()]));
+
+    // A line was added above and the distance changed, then another spawner is obstructed
+    let content = "version=54;\n    type=\"Land_Cargo20_red_F\";\ntype=\"Land_Cargo40_F\";\n";
+    mission.annotations = vec![
+        finding(
+            content,
+            "Land_Cargo20_red_F",
+            "Spawner is obstructed by a container 1.50m away",
+        ),
+        finding(
+            content,
+            "Land_Cargo40_F",
+            "Spawner is obstructed by a container 1.50m away",
+        ),
+    ];
+    let mut report = Report::new(vec![mission]);
+    assert_eq!(baseline.apply(&mut report), 1);
+    let remaining = report.annotations().collect::<Vec<_>>();
+    assert_eq!(remaining.len(), 1);
+    assert_eq!(remaining[0].start_line, 3);
+    std::fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,29 @@
+use std::path::PathBuf;
+
+use missionreviewer::{changes, settings::Settings};
+
+#[test]
+fn select() {
+    let settings = Settings::default();
+    let missions = vec![
+        PathBuf::from("contracts/CO30_Brett_Harmonics.pja308"),
+        PathBuf::from("contracts/CO40_Brett_Echoes.Altis"),
+    ];
+    let selected = changes::select(
+        &settings,
+        missions.clone(),
+        &[
+            PathBuf::from("contracts/CO30_Brett_Harmonics.pja308/mission.sqm"),
+            PathBuf::from("README.md"),
+        ],
+    );
+    assert_eq!(selected, missions[..1]);
+    let selected = changes::select(
+        &settings,
+        missions.clone(),
+        &[PathBuf::from("contracts/CO30_Brett_Harmonics.pja308.bak")],
+    );
+    assert!(selected.is_empty());
+    let selected = changes::select(&settings, missions.clone(), &[PathBuf::from("starts.txt")]);
+    assert_eq!(selected, missions);
+}
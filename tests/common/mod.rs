@@ -0,0 +1,12 @@
+use missionreviewer::{annotation::Annotation, rules};
+
+/// A guard waypoint finding in the Harmonics contract
+pub fn guard_waypoint(range: std::ops::Range<usize>) -> Annotation {
+    Annotation::new(
+        None,
+        "contracts/CO30_Brett_Harmonics.pja308/mission.sqm".to_string(),
+        range,
+        "Guard waypoint is not allowed".to_string(),
+        &rules::GUARD_WAYPOINT,
+    )
+}
@@ -0,0 +1,26 @@
+use std::path::PathBuf;
+
+use missionreviewer::{
+    checks::{objects::cover_map::CoverMapCheck, run_checks},
+    mission::read_mission,
+    rules,
+};
+
+#[test]
+fn waypoints_outside() {
+    let dir = PathBuf::from("tests/CoverMap_Waypoints.VR");
+    let (processed, report) = read_mission(&dir).unwrap();
+    let annotations = run_checks(
+        &dir,
+        vec![Box::new(CoverMapCheck::new(true, 0))],
+        (&processed, report.config()),
+    );
+    // Only the group with a unit outside, not the one with just a waypoint outside
+    assert_eq!(annotations.len(), 1);
+    assert_eq!(annotations[0].code, rules::COVER_MAP_OUTSIDE.code);
+    assert_eq!(
+        annotations[0].message,
+        "Units of a hostile group are outside of the cover map"
+    );
+    assert_eq!(annotations[0].start_line, 83);
+}
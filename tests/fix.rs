@@ -0,0 +1,105 @@
+use missionreviewer::{
+    annotation::Annotation,
+    fix::{self, Fix},
+    report::{MissionReport, Report},
+    rules,
+};
+
+const SQM: &str = "class Intel\n{\n\thour=11;\n\tminute=30;\n};\nclass Attributes\n{\n\tcondition=\"true\";\n};\n";
+
+fn find(text: &str) -> std::ops::Range<usize> {
+    let start = SQM.find(text).unwrap();
+    start..start + text.len()
+}
+
+#[test]
+fn apply() {
+    let hour = Fix::replace(None, &find("11"), "13").unwrap();
+    let minute = Fix::replace(None, &find("30"), "0").unwrap();
+    let offset = find("condition").start;
+    let server_only = Fix {
+        range: offset..offset,
+        replacement: "isServerOnly=1;\n\t".to_string(),
+    };
+    assert_eq!(
+        fix::apply(SQM, &[&minute, &server_only, &hour, &minute]),
+        "class Intel\n{\n\thour=13;\n\tminute=0;\n};\nclass Attributes\n{\n\tisServerOnly=1;\n\tcondition=\"true\";\n};\n"
+    );
+    assert!(Fix::replace(None, &(0..0), "1").is_none());
+}
+
+#[test]
+fn diff() {
+    let hour = Fix::replace(None, &find("11"), "13").unwrap();
+    let minute = Fix::replace(None, &find("30"), "0").unwrap();
+    let offset = find("condition").start;
+    let server_only = Fix {
+        range: offset..offset,
+        replacement: "isServerOnly=1;\n\t".to_string(),
+    };
+    assert_eq!(
+        fix::diff("mission.sqm", SQM, &[&hour, &minute, &server_only]),
+        "--- a/mission.sqm\n+++ b/mission.sqm\n\
+         @@ -3,2 +3,2 @@\n-\thour=11;\n-\tminute=30;\n+\thour=13;\n+\tminute=0;\n\
+         @@ -8,1 +8,2 @@\n-\tcondition=\"true\";\n+\tisServerOnly=1;\n+\tcondition=\"true\";\n"
+    );
+}
+
+#[test]
+fn run_keeps_skipped() {
+    let dir = std::env::temp_dir().join(format!("missionreviewer-fix-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("mission.sqm").display().to_string();
+    std::fs::write(&path, SQM).unwrap();
+    let annotation = |text: &str, replacement: &str| {
+        Annotation::new(
+            None,
+            path.clone(),
+            find(text),
+            format!("{} should be {}", text, replacement),
+            &rules::MISSION_INVALID,
+        )
+        .with_fix(Fix::replace(None, &find(text), replacement))
+    };
+    let mut mission = MissionReport::new(&dir);
+    mission.annotations = vec![
+        annotation("hour=11", "hour=13"),
+        // Overlaps the fix above, so it is skipped
+        annotation("11", "12"),
+        annotation("30", "0"),
+        Annotation::new(
+            None,
+            path.clone(),
+            find("condition"),
+            "No fix".to_string(),
+            &rules::MISSION_INVALID,
+        ),
+        Annotation::new(
+            None,
+            "released/CO30_Brett_Harmonics.pja308.pbo/mission.sqm".to_string(),
+            find("30"),
+            "In an archive".to_string(),
+            &rules::MISSION_INVALID,
+        )
+        .with_fix(Fix::replace(None, &find("30"), "0")),
+    ];
+    let mut report = Report::new(vec![mission]);
+    let summary = fix::run(&mut report, false);
+    let content = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(summary.unwrap(), "Applied 2 fix(es) in 1 file(s)\n");
+    assert!(content.contains("hour=13;") && content.contains("minute=0;"));
+    let remaining = report
+        .annotations()
+        .map(|annotation| annotation.message.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        remaining,
+        vec!["11 should be 12", "No fix", "In an archive"]
+    );
+    let lines = report
+        .annotations()
+        .map(|annotation| annotation.start_line)
+        .collect::<Vec<_>>();
+    assert_eq!(lines, vec![3, 8, 1]);
+}
@@ -39,7 +39,7 @@ fn observe_shops() {
     let annotations = run_checks(
         &PathBuf::from("tests/CO30_Brett_Harmonics.pja308"),
         vec![Box::new(
-            missionreviewer::checks::objects::shops::ShopCheck::new(),
+            missionreviewer::checks::objects::shops::ShopCheck::new(2, 100.0),
         )],
         (&mission.0, mission.1.config()),
     );
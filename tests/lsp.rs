@@ -0,0 +1,135 @@
+mod common;
+
+use std::path::Path;
+
+use missionreviewer::{
+    annotation::Annotation,
+    lsp::{self, Sources},
+    rules,
+};
+
+fn annotation() -> Annotation {
+    let mut annotation = common::guard_waypoint(0..0);
+    annotation.start_line = 12;
+    annotation.end_line = 12;
+    annotation.start_column = 5;
+    annotation.end_column = 14;
+    annotation
+}
+
+#[test]
+fn diagnostic() {
+    let diagnostic = lsp::diagnostic(&annotation(), Path::new("/work"), &mut Sources::default());
+    assert_eq!(diagnostic["range"]["start"]["line"], 11);
+    assert_eq!(diagnostic["range"]["start"]["character"], 4);
+    assert_eq!(diagnostic["range"]["end"]["character"], 13);
+    assert_eq!(diagnostic["severity"], 1);
+    assert_eq!(diagnostic["code"], "MR012");
+    assert!(diagnostic.get("relatedInformation").is_none());
+}
+
+#[test]
+fn related_information() {
+    let diagnostic = lsp::diagnostic(
+        &annotation().with_related(None, 0..0, "medium land spawner".to_string()),
+        Path::new("/work"),
+        &mut Sources::default(),
+    );
+    let related = &diagnostic["relatedInformation"][0];
+    assert_eq!(related["message"], "medium land spawner");
+    assert_eq!(related["location"]["range"]["start"]["line"], 0);
+    assert_eq!(
+        related["location"]["uri"],
+        "file:///work/contracts/CO30_Brett_Harmonics.pja308/mission.sqm"
+    );
+}
+
+#[test]
+fn hover() {
+    let annotations = [annotation()];
+    let hover = lsp::hover(
+        &annotations,
+        Path::new("/work"),
+        11,
+        6,
+        &mut Sources::default(),
+    )
+    .unwrap();
+    assert_eq!(
+        hover["contents"]["value"],
+        format!(
+            "**MR012 Guard waypoint**\n\n{}",
+            rules::GUARD_WAYPOINT.explanation
+        )
+    );
+    assert!(lsp::hover(
+        &annotations,
+        Path::new("/work"),
+        11,
+        13,
+        &mut Sources::default()
+    )
+    .is_none());
+    assert!(lsp::hover(
+        &annotations,
+        Path::new("/work"),
+        10,
+        6,
+        &mut Sources::default()
+    )
+    .is_none());
+}
+
+#[test]
+fn utf16() {
+    let root = std::env::temp_dir().join(format!("missionreviewer-lsp-{}", std::process::id()));
+    std::fs::create_dir_all(&root).unwrap();
+    // The helicopter is one character but two UTF-16 code units
+    std::fs::write(root.join("mission.sqm"), "name=\"Zugführer 🚁 Alpha\";\n").unwrap();
+    let mut annotation = annotation();
+    annotation.path = "mission.sqm".to_string();
+    annotation.start_line = 1;
+    annotation.end_line = 1;
+    // `Alpha`
+    annotation.start_column = 19;
+    annotation.end_column = 24;
+    let mut sources = Sources::default();
+    let diagnostic = lsp::diagnostic(&annotation, &root, &mut sources);
+    let annotations = [annotation];
+    let inside = lsp::hover(&annotations, &root, 0, 19, &mut sources).is_some();
+    let before = lsp::hover(&annotations, &root, 0, 18, &mut sources).is_some();
+    std::fs::remove_dir_all(&root).unwrap();
+    assert_eq!(diagnostic["range"]["start"]["character"], 19);
+    assert_eq!(diagnostic["range"]["end"]["character"], 24);
+    assert!(inside);
+    assert!(!before);
+}
+
+#[test]
+fn uris() {
+    let path = Path::new("/missions/CO30 Brett Harmonics.pja308/mission.sqm");
+    let uri = lsp::path_to_uri(path);
+    assert_eq!(
+        uri,
+        "file:///missions/CO30%20Brett%20Harmonics.pja308/mission.sqm"
+    );
+    assert_eq!(lsp::uri_to_path(&uri).unwrap(), path);
+    assert_eq!(
+        lsp::uri_to_path("file:///c%3A/missions").unwrap(),
+        Path::new("c:/missions")
+    );
+    assert_eq!(
+        lsp::normalize_uri("file:///c%3A/missions/CO30%20Brett").unwrap(),
+        lsp::path_to_uri(Path::new("c:/missions/CO30 Brett"))
+    );
+}
+
+#[test]
+fn read_message() {
+    let body = r#"{"jsonrpc":"2.0","method":"initialized","params":{}}"#;
+    let input = format!("Content-Length: {}\r\n\r\n{}", body.len(), body);
+    let mut input = input.as_bytes();
+    let message = lsp::read_message(&mut input).unwrap().unwrap();
+    assert_eq!(message["method"], "initialized");
+    assert!(lsp::read_message(&mut input).unwrap().is_none());
+}
@@ -0,0 +1,179 @@
+use std::{
+    cell::RefCell,
+    path::{Path, PathBuf},
+    rc::Rc,
+};
+
+use hemtt_config::{Class, Config};
+use hemtt_workspace::reporting::Processed;
+use missionreviewer::{
+    annotation::Annotation,
+    checks::{run_checks, Context, MissionCheck},
+    get_string,
+    mission::{
+        model::{Kind, Layer, Logic, Marker, Mission, Waypoint},
+        read_mission,
+        spatial::SpatialIndex,
+    },
+};
+
+fn mission() -> Mission {
+    let (_, report) = read_mission(&PathBuf::from("tests/Model_Entities.VR")).unwrap();
+    Mission::parse(report.config())
+}
+
+#[test]
+fn layers() {
+    let mission = mission();
+    let layer = mission.entity(0).unwrap();
+    assert_eq!(
+        layer.kind,
+        Kind::Layer(Layer {
+            name: Some("Shops".to_string())
+        })
+    );
+    assert!(layer.layers.is_empty());
+    assert_eq!(mission.entity(1).unwrap().layers, vec!["Shops".to_string()]);
+    assert!(mission.entity(3).unwrap().layers.is_empty());
+}
+
+#[test]
+fn groups() {
+    let mission = mission();
+    let Kind::Group(group) = &mission.entity(2).unwrap().kind else {
+        panic!("entity 2 is not a group");
+    };
+    assert_eq!(group.units, vec![3]);
+    assert_eq!(group.waypoints, vec![4]);
+    let unit = mission.entity(3).unwrap();
+    assert_eq!(unit.group, Some(2));
+    assert_eq!(unit.position_2d(), Some((10.0, 20.0)));
+    assert_eq!(unit.rotation, Some([0.0, 1.5, 0.0]));
+    assert!(unit.is_playable());
+    let Kind::Object(object) = &unit.kind else {
+        panic!("entity 3 is not an object");
+    };
+    assert_eq!(
+        object.description.as_ref().map(|d| d.value.as_str()),
+        Some("Rifleman")
+    );
+    let waypoint = mission.entity(4).unwrap();
+    assert_eq!(waypoint.group, Some(2));
+    assert_eq!(
+        waypoint.class_type.as_ref().map(|t| t.value.as_str()),
+        Some("Move")
+    );
+    assert_eq!(waypoint.position_2d(), Some((50.0, 60.0)));
+    assert_eq!(
+        waypoint.kind,
+        Kind::Waypoint(Waypoint {
+            condition: Some("true".to_string()),
+            on_activation: Some("hint 'moving'".to_string()),
+            completion_radius: Some(25.0),
+        })
+    );
+}
+
+#[test]
+fn markers() {
+    let mission = mission();
+    let marker = mission.entity(1).unwrap();
+    let Kind::Marker(Marker {
+        name,
+        marker_type,
+        size,
+        angle,
+    }) = &marker.kind
+    else {
+        panic!("entity 1 is not a marker");
+    };
+    assert_eq!(name.as_ref().map(|n| n.value.as_str()), Some("cover_map_0"));
+    assert_eq!(marker_type.as_deref(), Some("RECTANGLE"));
+    assert_eq!(*size, Some((250.0, 150.0)));
+    assert_eq!(*angle, Some(30.0));
+    assert_eq!(marker.position_2d(), Some((100.0, 200.0)));
+}
+
+#[test]
+fn logic() {
+    let mission = mission();
+    let module = mission.entity(5).unwrap();
+    assert_eq!(
+        module.kind,
+        Kind::Logic(Logic {
+            area_is_rectangle: true,
+            area_size: Some([20.0, 0.0, 40.0]),
+        })
+    );
+    assert_eq!(module.group, None);
+    assert_eq!(module.position_2d(), Some((300.0, 300.0)));
+}
+
+#[test]
+fn connections() {
+    let mission = mission();
+    assert_eq!(mission.connections.len(), 1);
+    let connection = &mission.connections[0];
+    assert_eq!((connection.item0, connection.item1), (5, 3));
+    assert_eq!(connection.connection_type.as_deref(), Some("Sync"));
+    assert_eq!(mission.entities.len(), 7);
+}
+
+/// Records the groups and layers it enters and leaves
+struct Hooks(Rc<RefCell<Vec<String>>>);
+
+impl Hooks {
+    fn record(&self, event: &str, class: &Class) {
+        let name = get_string(class, "name").map_or("group", |(name, _)| name);
+        self.0.borrow_mut().push(format!("{} {}", event, name));
+    }
+}
+
+impl MissionCheck for Hooks {
+    fn rule(&self) -> &'static str {
+        "hooks"
+    }
+
+    fn enter_group(&mut self, _: (&Processed, &Config), _: &Path, class: &Class, _: &Context) {
+        self.record("enter", class);
+    }
+
+    fn leave_group(&mut self, _: (&Processed, &Config), _: &Path, class: &Class, _: &Context) {
+        self.record("leave", class);
+    }
+
+    fn enter_layer(&mut self, _: (&Processed, &Config), _: &Path, class: &Class, _: &Context) {
+        self.record("enter", class);
+    }
+
+    fn leave_layer(&mut self, _: (&Processed, &Config), _: &Path, class: &Class, _: &Context) {
+        self.record("leave", class);
+    }
+
+    fn done(&self, _: (&Processed, &Config), _: &Path, _: &SpatialIndex) -> Vec<Annotation> {
+        Vec::new()
+    }
+}
+
+#[test]
+fn hooks() {
+    let dir = PathBuf::from("tests/Model_Entities.VR");
+    let (processed, report) = read_mission(&dir).unwrap();
+    let events = Rc::new(RefCell::new(Vec::new()));
+    run_checks(
+        &dir,
+        vec![Box::new(Hooks(events.clone()))],
+        (&processed, report.config()),
+    );
+    assert_eq!(
+        *events.borrow(),
+        vec![
+            "enter Shops",
+            "leave Shops",
+            "enter group",
+            "leave group",
+            "enter Empty",
+            "leave Empty",
+        ]
+    );
+}
@@ -0,0 +1,169 @@
+use std::path::{Path, PathBuf};
+
+use missionreviewer::{annotation::Level, pbo, rules, settings::Settings};
+
+const FIXTURE: &str = "tests/CO30_Brett_Harmonics.pja308";
+
+/// Copy the fixture mission into a new temporary folder, returning the folder and the copy
+fn copy_fixture(test: &str) -> (PathBuf, PathBuf) {
+    let temp =
+        std::env::temp_dir().join(format!("missionreviewer-{}-{}", test, std::process::id()));
+    let dir = temp.join("CO30_Brett_Harmonics.pja308");
+    for entry in walkdir::WalkDir::new(FIXTURE)
+        .into_iter()
+        .map(Result::unwrap)
+    {
+        let path = dir.join(entry.path().strip_prefix(FIXTURE).unwrap());
+        if entry.file_type().is_dir() {
+            std::fs::create_dir_all(path).unwrap();
+        } else {
+            std::fs::copy(entry.path(), path).unwrap();
+        }
+    }
+    (temp, dir)
+}
+
+#[test]
+fn archives() {
+    let dir = std::env::temp_dir().join(format!("missionreviewer-test-{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("released")).unwrap();
+    std::fs::write(dir.join("released/CO30_Brett_Harmonics.pja308.pbo"), b"").unwrap();
+    std::fs::write(dir.join("released/notes.txt"), b"").unwrap();
+    let folder = dir.join("released").display().to_string();
+    let (archives, filters) = pbo::archives(&[folder, "Harmonics".to_string()]);
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(
+        archives,
+        vec![dir.join("released/CO30_Brett_Harmonics.pja308.pbo")]
+    );
+    assert_eq!(filters, vec!["Harmonics".to_string()]);
+    assert!(!pbo::is_archive(&PathBuf::from("missing.pbo")));
+}
+
+#[test]
+fn dev_files() {
+    for path in [
+        "README.txt",
+        ".git/config",
+        "edit_me/.vscode/settings.json",
+        "mission.sqm.bak",
+    ] {
+        assert!(pbo::is_dev_file(Path::new(path)), "{}", path);
+    }
+    for path in [
+        "mission.sqm",
+        "edit_me/briefing/situation.html",
+        "readme.sqf",
+    ] {
+        assert!(!pbo::is_dev_file(Path::new(path)), "{}", path);
+    }
+}
+
+#[test]
+fn packed_check() {
+    let (temp, dir) = copy_fixture("packed-check");
+    let description = dir.join("edit_me/description.ext");
+    let content = std::fs::read_to_string(&description).unwrap();
+    std::fs::write(
+        &description,
+        content.replace("synixe_type = 0;", "synixe_type = 9;"),
+    )
+    .unwrap();
+    let archive = temp.join("CO30_Brett_Harmonics.pja308.pbo");
+    pbo::pack(&dir, &archive).unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    let report = pbo::check(&archive, &Settings::default());
+    std::fs::remove_dir_all(&temp).unwrap();
+    let report = report.unwrap();
+    let archive = archive.display().to_string();
+    assert_eq!(report.path, archive);
+    assert!(report.annotations.iter().any(|annotation| {
+        annotation.code == rules::UNKNOWN_TYPE.code
+            && annotation.path == format!("{}/edit_me/description.ext", archive)
+    }));
+    for annotation in &report.annotations {
+        assert!(
+            annotation.path.starts_with(&format!("{}/", archive)),
+            "{}",
+            annotation.path
+        );
+        assert!(annotation.fix.is_none());
+    }
+}
+
+#[test]
+fn round_trip() {
+    let (temp, dir) = copy_fixture("round-trip");
+    std::fs::create_dir_all(dir.join(".git")).unwrap();
+    std::fs::write(dir.join(".git/config"), b"").unwrap();
+    std::fs::write(dir.join("mission.sqm.bak"), b"").unwrap();
+    let archive = temp.join("CO30_Brett_Harmonics.pja308.pbo");
+    let count = pbo::pack(&dir, &archive).unwrap();
+    let pbo = hemtt_pbo::ReadablePbo::from(std::fs::File::open(&archive).unwrap()).unwrap();
+    let prefix = pbo.properties().get("prefix").cloned();
+    let files = pbo
+        .files()
+        .iter()
+        .map(|header| header.filename().to_string())
+        .collect::<Vec<_>>();
+    let unpacked = temp.join("unpacked");
+    let result = pbo::unpack(&archive, &unpacked);
+    let original = std::fs::read(dir.join("mission.sqm")).unwrap();
+    let restored = std::fs::read(unpacked.join("mission.sqm"));
+    let briefing = unpacked.join("edit_me/briefing.sqf").is_file();
+    std::fs::remove_dir_all(&temp).unwrap();
+    result.unwrap();
+    assert_eq!(prefix.as_deref(), Some("CO30_Brett_Harmonics.pja308"));
+    assert_eq!(files.len(), count);
+    assert!(files.contains(&"mission.sqm".to_string()));
+    assert!(files.contains(&"edit_me\\description.ext".to_string()));
+    for file in &files {
+        assert!(
+            !pbo::is_dev_file(Path::new(&file.replace('\\', "/"))),
+            "{}",
+            file
+        );
+    }
+    assert!(!files.contains(&"edit_me\\README.txt".to_string()));
+    assert_eq!(restored.unwrap(), original);
+    assert!(briefing);
+}
+
+#[test]
+fn archive_settings() {
+    let temp = std::env::temp_dir().join(format!(
+        "missionreviewer-archive-settings-{}",
+        std::process::id()
+    ));
+    let dir = temp.join("CO30_Brett_Empty.VR");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::create_dir_all(temp.join("released")).unwrap();
+    std::fs::write(dir.join("init.sqf"), b"").unwrap();
+    let archive = temp.join("released/CO30_Brett_Empty.VR.pbo");
+    pbo::pack(&dir, &archive).unwrap();
+    let released = temp.join("released").display().to_string();
+    let settings: Settings = format!(
+        "[[roots]]\npath = '{0}'\nprefixes = [\"TCO\"]\n\n\
+         [[overrides]]\npaths = ['{0}/*']\nrules = {{ mission-missing = \"notice\" }}\n",
+        released
+    )
+    .parse()
+    .unwrap();
+    let report = pbo::check(&archive, &settings);
+    std::fs::remove_dir_all(&temp).unwrap();
+    let report = report.unwrap();
+    let archive = archive.display().to_string();
+    let prefix = report
+        .annotations
+        .iter()
+        .find(|annotation| annotation.code == rules::INVALID_PREFIX.code)
+        .unwrap();
+    assert_eq!(prefix.path, archive);
+    let missing = report
+        .annotations
+        .iter()
+        .find(|annotation| annotation.code == rules::MISSION_MISSING.code)
+        .unwrap();
+    assert_eq!(missing.path, format!("{}/mission.sqm", archive));
+    assert_eq!(missing.level, Level::Notice);
+}
@@ -0,0 +1,85 @@
+use std::path::PathBuf;
+
+use hemtt_config::rapify::Rapify;
+use missionreviewer::{mission::read_mission, rapify};
+
+/// `version=54;`, `class Intel { hour=11; overcast=0.5; rain=1.0; };` and `items[]={"a",{1}};`
+fn mission() -> Vec<u8> {
+    let mut bytes = b"\0raP".to_vec();
+    bytes.extend(0_u32.to_le_bytes());
+    bytes.extend(8_u32.to_le_bytes());
+    bytes.extend(0_u32.to_le_bytes());
+    // Root class at 16
+    bytes.push(0); // parent
+    bytes.push(3); // entries
+    bytes.extend([1, 2]);
+    bytes.extend(b"version\0");
+    bytes.extend(54_i32.to_le_bytes());
+    bytes.push(0);
+    bytes.extend(b"Intel\0");
+    let offset_at = bytes.len();
+    bytes.extend(0_u32.to_le_bytes());
+    bytes.push(2);
+    bytes.extend(b"items\0");
+    bytes.push(2);
+    bytes.extend([0]);
+    bytes.extend(b"a\"b\0");
+    bytes.extend([3, 1, 2]);
+    bytes.extend(1_i32.to_le_bytes());
+    // Intel
+    let offset = bytes.len() as u32;
+    bytes[offset_at..offset_at + 4].copy_from_slice(&offset.to_le_bytes());
+    bytes.push(0);
+    bytes.push(3);
+    bytes.extend([1, 2]);
+    bytes.extend(b"hour\0");
+    bytes.extend(11_i32.to_le_bytes());
+    bytes.extend([1, 1]);
+    bytes.extend(b"overcast\0");
+    bytes.extend(0.5_f32.to_le_bytes());
+    bytes.extend([1, 1]);
+    bytes.extend(b"rain\0");
+    bytes.extend(1.0_f32.to_le_bytes());
+    bytes
+}
+
+#[test]
+fn derapify() {
+    let bytes = mission();
+    assert!(rapify::is_rapified(&bytes));
+    assert_eq!(
+        rapify::derapify(&bytes).unwrap(),
+        "version=54;\nclass Intel\n{\n\thour=11;\n\tovercast=0.5;\n\train=1.0;\n};\nitems[]={\"a\"\"b\",{1}};\n"
+    );
+}
+
+#[test]
+fn truncated() {
+    let bytes = mission();
+    assert!(rapify::derapify(&bytes[..bytes.len() - 2]).is_err());
+    assert!(!rapify::is_rapified(b"version=54;"));
+}
+
+/// Binarize a mission with HEMTT
+fn binarize(dir: &PathBuf) -> Vec<u8> {
+    let (_, report) = read_mission(dir).unwrap();
+    let mut bytes = Vec::new();
+    report.config().rapify(&mut bytes, 0).unwrap();
+    bytes
+}
+
+#[test]
+fn round_trip() {
+    let binarized = binarize(&PathBuf::from("tests/CO30_Brett_Harmonics.pja308"));
+    let dir = std::env::temp_dir().join(format!("missionreviewer-derapify-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("mission.sqm"),
+        rapify::derapify(&binarized).unwrap(),
+    )
+    .unwrap();
+    let again = binarize(&dir);
+    std::fs::remove_dir_all(&dir).unwrap();
+    // Every value, including the type and precision of numbers, survives the decoded text
+    assert!(binarized == again);
+}
@@ -0,0 +1,89 @@
+mod common;
+
+use std::path::Path;
+
+use missionreviewer::{
+    annotation::{Annotation, Level},
+    output::{json, junit, markdown, pretty},
+    report::{MissionReport, Report, EXIT_FINDINGS, EXIT_PARSE, SCHEMA_VERSION},
+    rules,
+};
+
+#[test]
+fn render() {
+    let mut mission = MissionReport::new(Path::new("contracts/CO30_Brett_Harmonics.pja308"));
+    mission.annotations.push(common::guard_waypoint(0..0));
+    let report: serde_json::Value =
+        serde_json::from_str(&json::render(&Report::new(vec![mission]))).unwrap();
+    assert_eq!(report["schema_version"], SCHEMA_VERSION);
+    assert_eq!(report["missions"][0]["map"], "pja308");
+    let annotation = &report["missions"][0]["annotations"][0];
+    assert_eq!(annotation["code"], "MR012");
+    assert_eq!(annotation["level"], "error");
+}
+
+#[test]
+fn render_junit() {
+    let mut mission = MissionReport::new(Path::new("contracts/CO30_Brett_Harmonics.pja308"));
+    mission.checks = vec!["triggers".to_string(), "cup-parking".to_string()];
+    mission.annotations.push(common::guard_waypoint(0..0));
+    let xml = junit::render(&Report::new(vec![mission]));
+    assert!(xml.contains(
+        "<testsuites name=\"missionreviewer\" tests=\"2\" failures=\"1\" skipped=\"0\">"
+    ));
+    assert!(xml.contains(
+        "<testcase classname=\"contracts/CO30_Brett_Harmonics.pja308\" name=\"cup-parking\"/>"
+    ));
+    assert!(xml.contains("<failure message=\"1 error(s)\" type=\"triggers\">"));
+}
+
+#[test]
+fn render_pretty() {
+    let mut mission = MissionReport::new(Path::new("tests/CO30_Brett_Harmonics.pja308"));
+    mission.annotations.push(Annotation::new(
+        None,
+        "tests/CO30_Brett_Harmonics.pja308/edit_me/description.ext".to_string(),
+        0..4,
+        "OnLoadName is not set".to_string(),
+        &rules::ON_LOAD_NAME,
+    ));
+    let out = pretty::render(&Report::new(vec![mission]), false);
+    assert!(out.contains("error[MR041]: OnLoadName is not set"));
+    assert!(out.contains("1 error(s), 0 warning(s), 0 notice(s) in 1 mission(s)"));
+}
+
+#[test]
+fn render_markdown() {
+    let mut mission = MissionReport::new(Path::new("contracts/CO30_Brett_Harmonics.pja308"));
+    mission.name = Some("Harmonics".to_string());
+    mission.synixe_type = Some(0);
+    mission.player_count = Some(30);
+    mission.annotations.push(common::guard_waypoint(0..0));
+    let md = markdown::render(&Report::new(vec![mission]));
+    assert!(md.contains(
+        "| ❌ | Harmonics<br>`contracts/CO30_Brett_Harmonics.pja308` | pja308 | 30 | Contract | 1 | 0 |"
+    ));
+    assert!(md.contains("<summary>Harmonics - 1 finding(s)</summary>"));
+    assert!(md.contains("- **error** `MR012` Guard waypoint: Guard waypoint is not allowed"));
+}
+
+#[test]
+fn exit_code() {
+    let mut mission = MissionReport::new(Path::new("contracts/CO30_Brett_Harmonics.pja308"));
+    mission.template_version = Some(3);
+    mission.annotations.push(Annotation::new(
+        None,
+        "contracts/CO30_Brett_Harmonics.pja308/mission.sqm".to_string(),
+        0..0,
+        "OnLoadMission should be a sentence".to_string(),
+        &rules::ON_LOAD_MISSION_STYLE,
+    ));
+    let report = Report::new(vec![mission.clone()]);
+    assert_eq!(report.exit_code(Level::Error), 0);
+    assert_eq!(report.exit_code(Level::Warning), EXIT_FINDINGS);
+    assert_eq!(report.exit_code(Level::Notice), EXIT_FINDINGS);
+
+    mission.template_version = None;
+    let report = Report::new(vec![mission]);
+    assert_eq!(report.exit_code(Level::Error), EXIT_PARSE);
+}
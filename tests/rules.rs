@@ -0,0 +1,31 @@
+use std::collections::HashSet;
+
+use missionreviewer::rules::{self, RULES};
+
+#[test]
+fn unique_codes() {
+    let mut seen = HashSet::new();
+    for rule in &RULES {
+        assert!(seen.insert(rule.id), "duplicate id {}", rule.id);
+        for code in rule.codes {
+            assert_eq!(
+                code.rule, rule.id,
+                "{} is listed under the wrong rule",
+                code.code
+            );
+            assert!(seen.insert(code.code), "duplicate code {}", code.code);
+            assert!(seen.insert(code.slug), "duplicate slug {}", code.slug);
+        }
+    }
+}
+
+#[test]
+fn lookup() {
+    assert_eq!(rules::code("guard-waypoint").unwrap().code, "MR012");
+    assert_eq!(
+        rules::code("MR012").unwrap().heading(),
+        "MR012 Guard waypoint"
+    );
+    assert!(rules::is_known("triggers"));
+    assert!(!rules::is_known("MR999"));
+}
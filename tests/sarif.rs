@@ -0,0 +1,18 @@
+mod common;
+
+use missionreviewer::output::sarif;
+
+#[test]
+fn render() {
+    let log: serde_json::Value =
+        serde_json::from_str(&sarif::render(&[common::guard_waypoint(0..0)])).unwrap();
+    assert_eq!(log["version"], "2.1.0");
+    let result = &log["runs"][0]["results"][0];
+    assert_eq!(result["ruleId"], "MR012");
+    assert_eq!(result["level"], "error");
+    let index = result["ruleIndex"].as_u64().unwrap() as usize;
+    assert_eq!(
+        log["runs"][0]["tool"]["driver"]["rules"][index]["id"],
+        "MR012"
+    );
+}
@@ -0,0 +1,187 @@
+use std::path::Path;
+
+use missionreviewer::{
+    annotation::{Annotation, Level},
+    mission, rules,
+    settings::Settings,
+};
+
+#[test]
+fn default_roots() {
+    let settings = Settings::default();
+    let root = settings
+        .root_for(Path::new("theatres/Harmonics/TCO30_Brett_Harmonics.pja308"))
+        .unwrap();
+    assert_eq!(root.prefixes, vec!["CCO", "TCO", "TT"]);
+    assert!(settings
+        .root_for(Path::new("theatres/TCO30_Brett_Harmonics.pja308"))
+        .is_none());
+}
+
+#[test]
+fn custom_roots() {
+    let settings: Settings = r#"
+        [[roots]]
+        path = "campaigns"
+        depth = 2
+        prefixes = ["CCO"]
+        types = [0, 1]
+    "#
+    .parse()
+    .unwrap();
+    assert_eq!(settings.roots.len(), 1);
+    let root = settings
+        .root_for(Path::new(
+            "campaigns/Harmonics/CCO30_Brett_Harmonics.pja308",
+        ))
+        .unwrap();
+    assert!(root.allows_type(1));
+    assert!(!root.allows_type(3));
+    assert_eq!(
+        root.check_prefix(Path::new("campaigns/Harmonics/CO30_Brett_Harmonics.pja308"))
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn severities() {
+    let settings: Settings = r#"
+        [rules]
+        cup-parking = "warning"
+        hostiles = "off"
+
+        [[overrides]]
+        paths = ["specials/*"]
+        rules = { hostiles = "error" }
+
+        [[overrides]]
+        types = [3]
+        rules = { spawners = "off" }
+    "#
+    .parse()
+    .unwrap();
+    let contract = settings.severities(Path::new("contracts/CO30_Brett_Harmonics.pja308"), Some(0));
+    assert!(contract.enabled("cup-parking"));
+    assert!(!contract.enabled("hostiles"));
+    assert!(contract.enabled("spawners"));
+    let special = settings.severities(Path::new("specials/CO30_Brett_Harmonics.pja308"), Some(3));
+    assert!(special.enabled("hostiles"));
+    assert!(!special.enabled("spawners"));
+}
+
+#[test]
+fn unknown_rule() {
+    assert!(r#"
+        [rules]
+        not-a-rule = "off"
+    "#
+    .parse::<Settings>()
+    .is_err());
+}
+
+#[test]
+fn thresholds() {
+    let settings: Settings = r#"
+        [thresholds]
+        hostile-distance = 800
+
+        [[overrides]]
+        types = [3]
+        thresholds = { hostile-distance = 200, required-shops = 1 }
+    "#
+    .parse()
+    .unwrap();
+    let contract = settings.thresholds(Path::new("contracts/CO30_Brett_Harmonics.pja308"), Some(0));
+    assert_eq!(contract.hostile_distance, 800.0);
+    let special = settings.thresholds(Path::new("specials/CO30_Brett_Harmonics.pja308"), Some(3));
+    assert_eq!(special.hostile_distance, 200.0);
+    assert_eq!(special.required_shops, 1);
+    assert_eq!(contract.required_shops, 2);
+    assert_eq!(Settings::default().thresholds.hostile_distance, 500.0);
+}
+
+#[test]
+fn shared_before_roots() {
+    let settings: Settings = r#"
+        shared = ["starts.txt", "functions/**"]
+
+        [[roots]]
+        path = "campaigns"
+    "#
+    .parse()
+    .unwrap();
+    assert_eq!(settings.shared, vec!["starts.txt", "functions/**"]);
+    assert_eq!(settings.roots.len(), 1);
+    assert!(settings.is_shared(Path::new("functions/fn_spawn.sqf")));
+}
+
+#[test]
+fn severities_without_mission() {
+    let settings: Settings = r#"
+        [rules]
+        mission-missing = "notice"
+
+        [[overrides]]
+        types = [0]
+        rules = { mission = "off" }
+    "#
+    .parse()
+    .unwrap();
+    let dir =
+        std::env::temp_dir().join(format!("missionreviewer-no-mission-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let report = mission::check(&dir, &settings);
+    std::fs::remove_dir_all(&dir).unwrap();
+    let report = report.unwrap();
+    assert_eq!(report.annotations.len(), 1);
+    assert_eq!(report.annotations[0].code, rules::MISSION_MISSING.code);
+    assert_eq!(report.annotations[0].level, Level::Notice);
+}
+
+#[test]
+fn override_wins_over_global_code() {
+    let settings: Settings = r#"
+        [rules]
+        guard-waypoint = "error"
+
+        [[overrides]]
+        paths = ["specials/*"]
+        rules = { triggers = "off" }
+    "#
+    .parse()
+    .unwrap();
+    let guard = || {
+        vec![Annotation::new(
+            None,
+            "mission.sqm".to_string(),
+            0..0,
+            "Guard waypoint is not allowed".to_string(),
+            &rules::GUARD_WAYPOINT,
+        )]
+    };
+    let contract = settings
+        .severities(Path::new("contracts/CO30_Brett_Harmonics.pja308"), Some(0))
+        .apply(guard());
+    assert_eq!(contract.len(), 1);
+    assert_eq!(contract[0].level, Level::Error);
+    let special = settings.severities(Path::new("specials/CO30_Brett_Harmonics.pja308"), Some(3));
+    assert!(!special.enabled("triggers"));
+    assert!(special.apply(guard()).is_empty());
+}
+
+#[test]
+fn prefixes_need_a_file() {
+    let dir = Path::new("contracts/Harmonics.pja308");
+    let invalid = || Settings::default().root_for(dir).unwrap().check_prefix(dir);
+    assert_eq!(invalid().len(), 1);
+    assert!(Settings::default()
+        .severities(dir, Some(0))
+        .apply(invalid())
+        .is_empty());
+    let configured: Settings = "".parse().unwrap();
+    assert_eq!(
+        configured.severities(dir, Some(0)).apply(invalid()).len(),
+        1
+    );
+}
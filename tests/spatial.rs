@@ -0,0 +1,90 @@
+use std::collections::HashMap;
+
+use missionreviewer::mission::{
+    model::{Entity, Kind, Mission},
+    spatial::SpatialIndex,
+};
+
+fn entity(id: i32, position: Option<[f32; 3]>) -> Entity {
+    Entity {
+        id: Some(id),
+        data_type: "Object".to_string(),
+        class_type: None,
+        side: None,
+        position,
+        rotation: None,
+        attributes: HashMap::new(),
+        custom_attributes: HashMap::new(),
+        group: None,
+        layers: Vec::new(),
+        span: 0..0,
+        kind: Kind::Other,
+    }
+}
+
+#[test]
+fn within() {
+    let mission = Mission::new(
+        vec![
+            entity(1, Some([100.0, 5.0, 100.0])),
+            entity(2, Some([130.0, 5.0, 140.0])),
+            entity(3, Some([149.0, 0.0, 100.0])),
+            entity(4, Some([-20.0, 0.0, 100.0])),
+            entity(5, None),
+        ],
+        Vec::new(),
+    );
+    let index = SpatialIndex::new(&mission);
+    let found = index
+        .within((100.0, 100.0), 50.0)
+        .into_iter()
+        .map(|(entity, distance)| (entity.id.unwrap(), distance))
+        .collect::<Vec<_>>();
+    assert_eq!(found, vec![(1, 0.0), (3, 49.0), (2, 50.0)]);
+    assert_eq!(index.positioned().count(), 4);
+    assert_eq!(mission.entity(5).unwrap().position, None);
+    assert!(mission.entity(6).is_none());
+}
+
+#[test]
+fn nearest() {
+    let mission = Mission::new(
+        vec![
+            entity(1, Some([0.0, 0.0, 0.0])),
+            entity(2, Some([1000.0, 0.0, 1000.0])),
+            entity(3, Some([900.0, 0.0, 1000.0])),
+        ],
+        Vec::new(),
+    );
+    let index = SpatialIndex::new(&mission);
+    let (entity, distance) = index
+        .nearest((1000.0, 1000.0), |entity| entity.id != Some(2))
+        .unwrap();
+    assert_eq!(entity.id, Some(3));
+    assert_eq!(distance, 100.0);
+    assert!(index.nearest((0.0, 0.0), |_| false).is_none());
+}
+
+#[test]
+fn far_away() {
+    let mission = Mission::new(
+        vec![
+            entity(1, Some([10.0, 0.0, 10.0])),
+            entity(2, Some([60.0, 0.0, 10.0])),
+            // Closer than entity 2 to the point below, but in a cell further out
+            entity(3, Some([-45.0, 0.0, 45.0])),
+        ],
+        Vec::new(),
+    );
+    let index = SpatialIndex::new(&mission);
+    assert_eq!(index.within((0.0, 0.0), f32::MAX).len(), 3);
+    assert_eq!(index.within((0.0, 0.0), 1.0e9).len(), 3);
+    assert!(index.within((1.0e7, 1.0e7), 10.0).is_empty());
+    let (entity, _) = index.nearest((-1.0e6, 10.0), |_| true).unwrap();
+    assert_eq!(entity.id, Some(3));
+    let (entity, distance) = index
+        .nearest((5.0, 40.0), |entity| entity.id != Some(1))
+        .unwrap();
+    assert_eq!(entity.id, Some(3));
+    assert!((distance - 50.25).abs() < 0.01);
+}
@@ -0,0 +1,27 @@
+use std::path::PathBuf;
+
+use missionreviewer::{
+    checks::{objects::spawners::SpawnersCheck, run_checks},
+    mission::read_mission,
+    rules,
+};
+
+#[test]
+fn props_near_spawner() {
+    let dir = PathBuf::from("tests/Spawner_Props.VR");
+    let (processed, report) = read_mission(&dir).unwrap();
+    let annotations = run_checks(
+        &dir,
+        vec![Box::new(SpawnersCheck::new(true, 3, false))],
+        (&processed, report.config()),
+    );
+    // The sign and the light are next to the spawner, only the container is reported
+    let obstructed = annotations
+        .iter()
+        .filter(|annotation| annotation.code == rules::SPAWNER_OBSTRUCTED.code)
+        .collect::<Vec<_>>();
+    assert_eq!(obstructed.len(), 1);
+    assert!(obstructed[0]
+        .message
+        .starts_with("Land_Cargo20_military_green_F is 4.0m"));
+}
@@ -0,0 +1,81 @@
+use missionreviewer::{
+    annotation::{Annotation, Level},
+    mission, rules,
+    settings::Settings,
+    suppression::{apply, parse},
+};
+
+#[test]
+fn parse_comments() {
+    let suppressions = parse(
+        "description.ext",
+        "synixe_type = 3;\n// missionreviewer: allow triggers, spawners\n// missionreviewer: allowed players\n",
+    );
+    assert_eq!(suppressions.len(), 2);
+    assert_eq!(suppressions[0].rule, "triggers");
+    assert_eq!(suppressions[1].rule, "spawners");
+    assert_eq!(suppressions[0].range, 17..61);
+}
+
+#[test]
+fn unused_suppressions() {
+    let suppressions = parse(
+        "description.ext",
+        "// missionreviewer: allow guard-waypoint spawners\n",
+    );
+    let messages = apply(
+        &suppressions,
+        vec![Annotation::new(
+            None,
+            "mission.sqm".to_string(),
+            0..0,
+            "Guard waypoint is not allowed".to_string(),
+            &rules::GUARD_WAYPOINT,
+        )],
+    );
+    assert_eq!(messages.len(), 1);
+    assert_eq!(messages[0].level, Level::Notice);
+    assert_eq!(messages[0].title, "MR011 Unused suppression");
+}
+
+#[test]
+fn unknown_suppressions() {
+    let suppressions = parse(
+        "description.ext",
+        "// missionreviewer: allow guard-waypoints MR011\n",
+    );
+    let messages = apply(&suppressions, vec![]);
+    assert_eq!(messages.len(), 2);
+    assert_eq!(messages[0].level, Level::Warning);
+    assert_eq!(messages[0].title, "MR054 Unknown suppression");
+    assert!(messages[0].message.contains("`guard-waypoints`"));
+    assert_eq!(messages[1].title, "MR011 Unused suppression");
+}
+
+#[test]
+fn suppressions_off() {
+    let dir = std::env::temp_dir().join(format!(
+        "missionreviewer-suppressions-off-{}",
+        std::process::id()
+    ));
+    std::fs::create_dir_all(dir.join("edit_me")).unwrap();
+    std::fs::write(
+        dir.join("edit_me/description.ext"),
+        "// missionreviewer: allow not-a-rule guard-waypoint\n",
+    )
+    .unwrap();
+    let check = |settings: &str| {
+        mission::check(&dir, &settings.parse::<Settings>().unwrap())
+            .unwrap()
+            .annotations
+            .into_iter()
+            .map(|annotation| annotation.code)
+            .collect::<Vec<_>>()
+    };
+    let on = check("");
+    let off = check("[rules]\nsuppressions = \"off\"\n");
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert!(on.contains(&rules::UNUSED_SUPPRESSION.code.to_string()));
+    assert!(on.contains(&rules::UNKNOWN_SUPPRESSION.code.to_string()));
+    assert_eq!(off, vec![rules::MISSION_MISSING.code.to_string()]);
+}